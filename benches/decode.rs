@@ -0,0 +1,32 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use nmea::{BulkParser, NMEASentenceReader};
+use rayon::prelude::*;
+
+/// About 10 MB of a typical GNSS and sounder recording
+fn recording() -> Vec<u8> {
+    let sentences: &[u8] =
+        b"$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76\r\n\
+$GPGSA,A,3,32,21,,,,,,,,,,,1.50,0.90,1.20*0E\r\n\
+$GPGSV,2,1,05,01,40,083,46,02,17,308,41,12,07,344,,14,22,228,45*72\r\n\
+$SDDPT,87.4,0.0,*40\r\n";
+    sentences.repeat(10_000_000 / sentences.len())
+}
+
+fn decode(c: &mut Criterion) {
+    let data = recording();
+    let mut group = c.benchmark_group("decode");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    group.sample_size(10);
+
+    group.bench_function("NMEASentenceReader", |b| {
+        b.iter(|| NMEASentenceReader::new(&data[..]).count())
+    });
+    let parser = BulkParser::new();
+    group.bench_function("BulkParser", |b| {
+        b.iter(|| parser.par_sentences(&data).count())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, decode);
+criterion_main!(benches);
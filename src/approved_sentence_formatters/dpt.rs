@@ -1,60 +1,104 @@
-use std::str::FromStr;
+#[cfg(feature = "alloc")]
+use crate::{Encode, FieldWriter, NMEASentenceFormatter};
+use crate::{Fields, ParseError};
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct DPT {
-    depth: f32,       // Water depth relative to the transducer, meters
-    offset: f32, // Offset from transducer1,2, meters 1) "positive" = distance from transducer to water-line, "-" = distance from transducer to keel 2) For IEC applications the offset shall always be applied to provide depth relative to the keel.
-    range_scale: f32, // Maximum range scale in use
+    pub depth: Option<f32>,       // Water depth relative to the transducer, meters
+    pub offset: Option<f32>, // Offset from transducer1,2, meters 1) "positive" = distance from transducer to water-line, "-" = distance from transducer to keel 2) For IEC applications the offset shall always be applied to provide depth relative to the keel.
+    pub range_scale: Option<f32>, // Maximum range scale in use
 }
 
 impl DPT {
-    pub fn from_field(fields: Vec<&[u8]>) -> Self {
-        let depth = f32::from_str(
-            &*fields[0]
-                .iter()
-                .map(|byte| *byte as char)
-                .collect::<String>(),
-        )
-        .unwrap_or_default();
-        let offset = f32::from_str(
-            &*fields[1]
-                .iter()
-                .map(|byte| *byte as char)
-                .collect::<String>(),
-        )
-        .unwrap_or_default();
-        let range_scale = f32::from_str(
-            &*fields[2]
-                .iter()
-                .map(|byte| *byte as char)
-                .collect::<String>(),
-        )
-        .unwrap_or_default();
-        Self {
-            depth,
-            offset,
-            range_scale,
+    pub fn from_field(fields: &[&[u8]]) -> Result<Self, ParseError> {
+        if fields.len() < 2 {
+            return Err(ParseError::MissingField(fields.len()));
         }
+        Ok(Self {
+            depth: fields.f32(0)?,
+            offset: fields.f32(1)?,
+            // Range scale was only added in NMEA 3.0 and is usually a null field
+            range_scale: fields.f32(2)?,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encode for DPT {
+    fn formatter(&self) -> NMEASentenceFormatter {
+        ['D', 'P', 'T']
+    }
+
+    fn encode_fields(&self, writer: &mut FieldWriter) {
+        writer
+            .float(self.depth, 1)
+            .float(self.offset, 1)
+            .float(self.range_scale, 1);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::approved_sentence_formatters::dpt::DPT;
+    use crate::ParseError;
+    use crate::{Encode, Talker};
 
     #[test]
     fn test_parse_dpt() {
         let fields: Vec<&[u8]> = vec![b"87.4", b"0.0", b""];
 
-        let dpt = DPT::from_field(fields);
+        let dpt = DPT::from_field(&fields).unwrap();
 
         let expected = DPT {
-            offset: 0.0,
-            depth: 87.4,
-            range_scale: 0.0,
+            offset: Some(0.0),
+            depth: Some(87.4),
+            range_scale: None,
         };
 
         assert_eq!(dpt.offset, expected.offset);
         assert_eq!(dpt.depth, expected.depth);
         assert_eq!(dpt.range_scale, expected.range_scale);
     }
+
+    #[test]
+    fn test_parse_dpt_errors() {
+        let fields: Vec<&[u8]> = vec![b"87.4"];
+        assert_eq!(
+            DPT::from_field(&fields).unwrap_err(),
+            ParseError::MissingField(1)
+        );
+
+        let fields: Vec<&[u8]> = vec![b"87.4", b"0.x"];
+        assert_eq!(
+            DPT::from_field(&fields).unwrap_err(),
+            ParseError::UnparsableField {
+                index: 1,
+                offset: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let dpt = DPT {
+            depth: Some(87.4),
+            offset: Some(-1.2),
+            range_scale: None,
+        };
+
+        let sentence = dpt.to_sentence(Talker::Sounder).unwrap();
+        assert_eq!(sentence.as_bytes(), b"$SDDPT,87.4,-1.2,*6E\r\n");
+
+        let content = sentence.decode().unwrap();
+        assert_eq!(DPT::from_field(&content.fields().unwrap()).unwrap(), dpt);
+
+        let dpt = DPT {
+            range_scale: Some(2.5),
+            ..dpt
+        };
+        let sentence = dpt.to_sentence(Talker::Sounder).unwrap();
+        assert_eq!(sentence.as_bytes(), b"$SDDPT,87.4,-1.2,2.5*47\r\n");
+        let content = sentence.decode().unwrap();
+        assert_eq!(DPT::from_field(&content.fields().unwrap()).unwrap(), dpt);
+    }
 }
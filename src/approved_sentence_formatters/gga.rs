@@ -1,10 +1,11 @@
 use crate::primitives::coordinates::Coordinate;
+#[cfg(feature = "alloc")]
+use crate::{Encode, FieldWriter, NMEASentenceFormatter, ToField};
+use crate::{Fields, FromField, ParseError};
 use chrono::NaiveTime;
-use std::f32;
-use std::str::FromStr;
 
 /// Different GPS Quality types\
-/// [SPS](GPSQuality::Invalid) Fix not available or invalid\
+/// [Invalid](GPSQuality::Invalid) Fix not available or invalid\
 /// [SPS](GPSQuality::SPS) GPS SPS Mode, fix valid\
 /// [Differential](GPSQuality::Differential) Differential GPS, SPS Mode, fix valid\
 /// [PPS](GPSQuality::PPS) GPS PPS Mode, fix valid\
@@ -13,7 +14,7 @@ use std::str::FromStr;
 /// [Estimated](GPSQuality::Estimated) Estimated (dead reckoning) Mode\
 /// [Manual](GPSQuality::Manual) Manual Input Mode\
 /// [Simulator](GPSQuality::Simulator) Simulator Mode
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GPSQuality {
     Invalid,
     SPS,
@@ -24,7 +25,15 @@ pub enum GPSQuality {
     Estimated,
     Manual,
     Simulator,
-    None,
+}
+
+impl FromField for GPSQuality {
+    fn from_field(field: &[u8]) -> Option<Self> {
+        match field {
+            [byte @ b'0'..=b'8'] => Some(GPSQuality::from_char(*byte)),
+            _ => None,
+        }
+    }
 }
 
 impl GPSQuality {
@@ -55,130 +64,51 @@ impl GPSQuality {
 /// [geoidal_separation](GGS.geoidal_separation) Geoidal separation, meters (Geoidal Separation: the difference between the WGS-84 earth ellipsoid surface and mean-sea-level (geoid) surface, "-" = mean-sea-level surface below WGS-84 ellipsoid surface)\
 /// [age_of_differential_gps](GGS.age_of_differential_gps) Age of Differential GPS data (Time in seconds since last SC104 Type 1 or 9 update, null field when DGPS is not used300)\
 /// [differential_station_id](GGS.differential_station_id) Differential reference station ID, 0000-1023\
+#[derive(Debug, Clone, PartialEq)]
 pub struct GGA {
-    pub time: NaiveTime,
-    pub latitude: Coordinate,
-    pub longitude: Coordinate,
-    pub gps_quality: GPSQuality,
-    pub satellites_in_use: u8,
-    pub hdop: f32,
-    pub altitude: f32,
-    pub age_of_differential_gps: f32,
-    pub differential_station_id: u16,
-    pub geoidal_separation: f32,
+    pub time: Option<NaiveTime>,
+    pub latitude: Option<Coordinate>,
+    pub longitude: Option<Coordinate>,
+    pub gps_quality: Option<GPSQuality>,
+    pub satellites_in_use: Option<u8>,
+    pub hdop: Option<f32>,
+    pub altitude: Option<f32>,
+    pub age_of_differential_gps: Option<f32>,
+    pub differential_station_id: Option<u16>,
+    pub geoidal_separation: Option<f32>,
 }
 
 impl GGA {
-    pub fn from_field(fields: Vec<&[u8]>) -> Self {
-        let mut gps_quality = GPSQuality::None;
-
-        let time = NaiveTime::parse_from_str(
-            &*fields[0]
-                .iter()
-                .map(|byte| *byte as char)
-                .collect::<String>(),
-            "%H%M%S%.f",
-        )
-        .unwrap_or_else(|_| {
-            NaiveTime::parse_from_str(
-                &*fields[0]
-                    .iter()
-                    .map(|byte| *byte as char)
-                    .collect::<String>(),
-                "%H%M%S",
-            )
-            .unwrap_or_default()
-        });
-
-        let latitude = Coordinate::from_latitude_string(
-            fields[1]
-                .iter()
-                .map(|byte| *byte as char)
-                .collect::<String>(),
-            *fields[2].get(0).unwrap_or(&b'X') as char,
-        )
-        .unwrap_or_else(|_| {
-            gps_quality = GPSQuality::Invalid;
-            Coordinate::default()
-        });
-
-        let longitude = Coordinate::from_longitude_string(
-            fields[3]
-                .iter()
-                .map(|byte| *byte as char)
-                .collect::<String>(),
-            *fields[4].get(0).unwrap_or(&b'X') as char,
-        )
-        .unwrap_or_else(|_| {
-            gps_quality = GPSQuality::Invalid;
-            Coordinate::default()
-        });
-
-        let satellites_in_use = u8::from_str(
-            &*fields[6]
-                .iter()
-                .map(|byte| *byte as char)
-                .collect::<String>(),
-        )
-        .unwrap_or_else(|_| {
-            gps_quality = GPSQuality::Invalid;
-            0
-        });
-
-        let hdop = f32::from_str(
-            &*fields[7]
-                .iter()
-                .map(|byte| *byte as char)
-                .collect::<String>(),
-        )
-        .unwrap_or_else(|_| {
-            gps_quality = GPSQuality::Invalid;
-            0.0
-        });
-
-        let altitude = f32::from_str(
-            &*fields[8]
-                .iter()
-                .map(|byte| *byte as char)
-                .collect::<String>(),
-        )
-        .unwrap_or_else(|_| {
-            gps_quality = GPSQuality::Invalid;
-            0.0
-        });
-
-        let geoidal_separation = f32::from_str(
-            &*fields[10]
-                .iter()
-                .map(|byte| *byte as char)
-                .collect::<String>(),
-        )
-        .unwrap_or(0.0);
-
-        let age_of_differential_gps = f32::from_str(
-            &*fields[12]
-                .iter()
-                .map(|byte| *byte as char)
-                .collect::<String>(),
-        )
-        .unwrap_or(0.0);
-
-        let differential_station_id = u16::from_str(
-            &*fields[13]
-                .iter()
-                .map(|byte| *byte as char)
-                .collect::<String>(),
-        )
-        .unwrap_or(0);
-
-        match gps_quality {
-            GPSQuality::None => {
-                gps_quality = GPSQuality::from_char(fields[5][0]);
-            }
-            _ => {}
+    pub fn from_field(fields: &[&[u8]]) -> Result<Self, ParseError> {
+        if fields.len() < 14 {
+            return Err(ParseError::MissingField(fields.len()));
         }
+        let time = fields.time(0)?;
+        let gps_quality = fields.enumeration(5)?;
+        let latitude = fields.latitude(1)?;
+        let longitude = fields.longitude(3)?;
+        let satellites_in_use = fields.u8(6)?;
+        let hdop = fields.f32(7)?;
+        let altitude = fields.f32(8)?;
+        let geoidal_separation = fields.f32(10)?;
+        let age_of_differential_gps = fields.f32(12)?;
+        let differential_station_id = fields.u16(13)?;
+
+        // Without a complete position the fix is unusable, whatever the quality field says
+        let complete = latitude.is_some()
+            && longitude.is_some()
+            && satellites_in_use.is_some()
+            && hdop.is_some()
+            && altitude.is_some();
+        let gps_quality = gps_quality.map(|quality| {
+            if complete {
+                quality
+            } else {
+                GPSQuality::Invalid
+            }
+        });
 
-        Self {
+        Ok(Self {
             time,
             latitude,
             longitude,
@@ -189,10 +119,129 @@ impl GGA {
             age_of_differential_gps,
             differential_station_id,
             geoidal_separation,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToField for GPSQuality {
+    fn to_field(&self) -> &'static str {
+        match self {
+            GPSQuality::Invalid => "0",
+            GPSQuality::SPS => "1",
+            GPSQuality::Differential => "2",
+            GPSQuality::PPS => "3",
+            GPSQuality::RTKFixed => "4",
+            GPSQuality::RTKFloat => "5",
+            GPSQuality::Estimated => "6",
+            GPSQuality::Manual => "7",
+            GPSQuality::Simulator => "8",
         }
     }
 }
 
+#[cfg(feature = "alloc")]
+impl Encode for GGA {
+    fn formatter(&self) -> NMEASentenceFormatter {
+        ['G', 'G', 'A']
+    }
+
+    fn encode_fields(&self, writer: &mut FieldWriter) {
+        writer
+            .time(self.time)
+            .latitude(self.latitude)
+            .longitude(self.longitude)
+            .enumeration(self.gps_quality.as_ref())
+            .uint(self.satellites_in_use, 2)
+            .float(self.hdop, 1)
+            .float(self.altitude, 1)
+            .char(self.altitude.map(|_| 'M'))
+            .float(self.geoidal_separation, 1)
+            .char(self.geoidal_separation.map(|_| 'M'))
+            .float(self.age_of_differential_gps, 1)
+            .uint(self.differential_station_id, 4);
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::approved_sentence_formatters::gga::{GPSQuality, GGA};
+    use crate::{Encode, ParseError, Talker};
+    use chrono::NaiveTime;
+
+    #[test]
+    fn test_parse_gga() {
+        let fields: Vec<&[u8]> = vec![
+            b"123519",
+            b"4807.038",
+            b"N",
+            b"01131.000",
+            b"E",
+            b"1",
+            b"08",
+            b"0.9",
+            b"545.4",
+            b"M",
+            b"46.9",
+            b"M",
+            b"",
+            b"",
+        ];
+
+        let gga = GGA::from_field(&fields).unwrap();
+
+        assert_eq!(gga.time, NaiveTime::from_hms_opt(12, 35, 19));
+        assert_eq!(gga.latitude.unwrap().degrees(), 48);
+        assert_eq!(gga.longitude.unwrap().direction(), 'E');
+        assert_eq!(gga.gps_quality, Some(GPSQuality::SPS));
+        assert_eq!(gga.satellites_in_use, Some(8));
+        assert_eq!(gga.altitude, Some(545.4));
+        assert_eq!(gga.geoidal_separation, Some(46.9));
+        assert_eq!(gga.age_of_differential_gps, None);
+        assert_eq!(gga.differential_station_id, None);
+
+        let mut fields = fields;
+        fields[8] = b"";
+        let gga = GGA::from_field(&fields).unwrap();
+        assert_eq!(gga.gps_quality, Some(GPSQuality::Invalid));
+        assert_eq!(gga.altitude, None);
+
+        fields[5] = b"";
+        assert_eq!(GGA::from_field(&fields).unwrap().gps_quality, None);
+        fields[5] = b"9";
+        assert_eq!(
+            GGA::from_field(&fields).unwrap_err(),
+            ParseError::unparsable_field(&fields, 5)
+        );
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let fields: Vec<&[u8]> = vec![
+            b"123519",
+            b"4807.038",
+            b"N",
+            b"01131.000",
+            b"E",
+            b"1",
+            b"08",
+            b"0.9",
+            b"545.4",
+            b"M",
+            b"46.9",
+            b"M",
+            b"",
+            b"",
+        ];
+        let gga = GGA::from_field(&fields).unwrap();
+
+        let sentence = gga.to_sentence(Talker::Gps).unwrap();
+        assert_eq!(
+            sentence.as_bytes(),
+            b"$GPGGA,123519.00,4807.0380,N,01131.0000,E,1,08,0.9,545.4,M,46.9,M,,*69\r\n"
+        );
+
+        let content = sentence.decode().unwrap();
+        assert_eq!(GGA::from_field(&content.fields().unwrap()).unwrap(), gga);
+    }
 }
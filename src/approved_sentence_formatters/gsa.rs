@@ -1,89 +1,119 @@
-use std::str::FromStr;
+use crate::approved_sentence_formatters::{hex_digit, SatelliteId};
+use crate::primitives::inline_array::InlineArray;
+#[cfg(feature = "alloc")]
+use crate::{Encode, FieldWriter, NMEASentenceFormatter, ToField};
+use crate::{Fields, FromField, ParseError};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GSAOperationModeConfig {
-    Automatic, //Manual, forced to operate in 2D or 3D mode
-    Manuel,    // Automatic, allowed to automatically switch 2D/3D
-    Invalid,
+    Automatic, // Automatic, allowed to automatically switch 2D/3D
+    Manuel,    // Manual, forced to operate in 2D or 3D mode
 }
-impl GSAOperationModeConfig {
-    fn from_field(field: &[u8]) -> Self {
+impl FromField for GSAOperationModeConfig {
+    fn from_field(field: &[u8]) -> Option<Self> {
         match field {
-            b"M" => GSAOperationModeConfig::Manuel,
-            b"A" => GSAOperationModeConfig::Automatic,
-            _ => GSAOperationModeConfig::Invalid,
+            b"M" => Some(GSAOperationModeConfig::Manuel),
+            b"A" => Some(GSAOperationModeConfig::Automatic),
+            _ => None,
         }
     }
 }
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GSAOperationMode {
     FixNotAvailable,
     TwoDimensional,   // 2D
     ThreeDimensional, // 3D
-    Invalid,
 }
 
-impl GSAOperationMode {
-    fn from_field(field: &[u8]) -> Self {
+impl FromField for GSAOperationMode {
+    fn from_field(field: &[u8]) -> Option<Self> {
         match field {
-            b"1" => GSAOperationMode::FixNotAvailable,
-            b"2" => GSAOperationMode::TwoDimensional,
-            b"3" => GSAOperationMode::ThreeDimensional,
-            _ => GSAOperationMode::Invalid,
+            b"1" => Some(GSAOperationMode::FixNotAvailable),
+            b"2" => Some(GSAOperationMode::TwoDimensional),
+            b"3" => Some(GSAOperationMode::ThreeDimensional),
+            _ => None,
         }
     }
 }
 
-#[derive(Debug)]
+/// Satellites used in the solution, at most the twelve fields of a `GSA` sentence
+pub type SatelliteIds = InlineArray<SatelliteId, 12>;
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct GSA {
-    config: GSAOperationModeConfig,
-    mode: GSAOperationMode,
-    satellite_ids: Vec<u8>,
-    pdop: f32,
-    hdop: f32,
-    vdop: f32,
+    pub config: Option<GSAOperationModeConfig>,
+    pub mode: Option<GSAOperationMode>,
+    pub satellite_ids: SatelliteIds,
+    pub pdop: Option<f32>,
+    pub hdop: Option<f32>,
+    pub vdop: Option<f32>,
+    pub system_id: Option<u8>, // NMEA 4.10 GNSS system id, e.g. 1 for GPS
 }
 
 impl GSA {
-    pub fn from_field(fields: Vec<&[u8]>) -> Self {
-        let vdop = f32::from_str(
-            &*fields[fields.len() - 1]
-                .iter()
-                .map(|byte| *byte as char)
-                .collect::<String>(),
-        )
-        .unwrap_or_default();
-        let hdop = f32::from_str(
-            &*fields[fields.len() - 2]
-                .iter()
-                .map(|byte| *byte as char)
-                .collect::<String>(),
-        )
-        .unwrap_or_default();
-        let pdop = f32::from_str(
-            &*fields[fields.len() - 3]
-                .iter()
-                .map(|byte| *byte as char)
-                .collect::<String>(),
-        )
-        .unwrap_or_default();
-
-        Self {
-            config: GSAOperationModeConfig::from_field(fields[0]),
-            mode: GSAOperationMode::from_field(fields[1]),
-            satellite_ids: fields
-                .iter()
-                .skip(2)
-                .take_while(|&&field| !field.contains(&b'.'))
-                .filter(|&&field| field != b"")
-                .map(|field| {
-                    u8::from_str(&*field.iter().map(|byte| *byte as char).collect::<String>())
-                        .unwrap_or_default()
-                })
-                .collect::<Vec<u8>>(),
-            pdop,
-            hdop,
-            vdop,
+    pub fn from_field(fields: &[&[u8]]) -> Result<Self, ParseError> {
+        if fields.len() < 17 {
+            return Err(ParseError::MissingField(fields.len()));
+        }
+        // Twelve satellite id fields, unused ones are null. NMEA 4.1 appends the GNSS system id.
+        let mut ids = [None; 12];
+        for (index, id) in ids.iter_mut().enumerate() {
+            *id = fields.u16(index + 2)?;
+        }
+        let satellite_ids = ids.into_iter().flatten().collect();
+
+        Ok(Self {
+            config: fields.enumeration(0)?,
+            mode: fields.enumeration(1)?,
+            satellite_ids,
+            pdop: fields.f32(14)?,
+            hdop: fields.f32(15)?,
+            vdop: fields.f32(16)?,
+            system_id: hex_digit(fields, 17)?,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToField for GSAOperationModeConfig {
+    fn to_field(&self) -> &'static str {
+        match self {
+            GSAOperationModeConfig::Automatic => "A",
+            GSAOperationModeConfig::Manuel => "M",
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToField for GSAOperationMode {
+    fn to_field(&self) -> &'static str {
+        match self {
+            GSAOperationMode::FixNotAvailable => "1",
+            GSAOperationMode::TwoDimensional => "2",
+            GSAOperationMode::ThreeDimensional => "3",
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encode for GSA {
+    fn formatter(&self) -> NMEASentenceFormatter {
+        ['G', 'S', 'A']
+    }
+
+    fn encode_fields(&self, writer: &mut FieldWriter) {
+        writer
+            .enumeration(self.config.as_ref())
+            .enumeration(self.mode.as_ref());
+        for index in 0..12 {
+            writer.uint(self.satellite_ids.get(index).copied(), 2);
+        }
+        writer
+            .float(self.pdop, 2)
+            .float(self.hdop, 2)
+            .float(self.vdop, 2);
+        if let Some(system_id) = self.system_id {
+            writer.hex(Some(system_id.into()), 1);
         }
     }
 }
@@ -91,7 +121,7 @@ impl GSA {
 #[cfg(test)]
 mod tests {
     use crate::approved_sentence_formatters::gsa::{GSAOperationMode, GSAOperationModeConfig, GSA};
-    use crate::{NMEASentence, NMEA_SENTENCE_MAX_LENGTH};
+    use crate::{Encode, Talker};
 
     #[test]
     fn test_parse_gsa() {
@@ -100,15 +130,18 @@ mod tests {
             b"72", b"", b"1.50", b"0.90", b"1.20",
         ];
 
-        let gsa = GSA::from_field(fields);
+        let gsa = GSA::from_field(&fields).unwrap();
 
         let expected = GSA {
-            config: GSAOperationModeConfig::Automatic,
-            mode: GSAOperationMode::ThreeDimensional,
-            satellite_ids: Vec::from([32, 21, 22, 1, 3, 31, 4, 17, 8, 71, 72]),
-            pdop: 1.5,
-            hdop: 0.9,
-            vdop: 1.2,
+            config: Some(GSAOperationModeConfig::Automatic),
+            mode: Some(GSAOperationMode::ThreeDimensional),
+            satellite_ids: [32, 21, 22, 1, 3, 31, 4, 17, 8, 71, 72]
+                .into_iter()
+                .collect(),
+            pdop: Some(1.5),
+            hdop: Some(0.9),
+            vdop: Some(1.2),
+            system_id: None,
         };
 
         assert_eq!(gsa.satellite_ids, expected.satellite_ids);
@@ -118,4 +151,30 @@ mod tests {
         assert_eq!(gsa.vdop, expected.vdop);
         assert_eq!(gsa.hdop, expected.hdop);
     }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let fields: Vec<&[u8]> = vec![
+            b"A", b"3", b"32", b"21", b"", b"", b"", b"", b"", b"", b"", b"", b"", b"", b"1.50",
+            b"0.90", b"1.20",
+        ];
+        let gsa = GSA::from_field(&fields).unwrap();
+
+        let sentence = gsa.to_sentence(Talker::Gps).unwrap();
+        assert_eq!(
+            sentence.as_bytes(),
+            b"$GPGSA,A,3,32,21,,,,,,,,,,,1.50,0.90,1.20*0E\r\n"
+        );
+
+        let content = sentence.decode().unwrap();
+        assert_eq!(GSA::from_field(&content.fields().unwrap()).unwrap(), gsa);
+
+        let fields = [&fields[..], &[b"1"]].concat();
+        let gsa = GSA::from_field(&fields).unwrap();
+        assert_eq!(gsa.system_id, Some(1));
+        assert_eq!(
+            gsa.to_sentence(Talker::Gnss).unwrap().as_bytes(),
+            b"$GNGSA,A,3,32,21,,,,,,,,,,,1.50,0.90,1.20,1*0D\r\n"
+        );
+    }
 }
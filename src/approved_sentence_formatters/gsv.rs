@@ -0,0 +1,272 @@
+use crate::approved_sentence_formatters::{hex_digit, SatelliteId};
+use crate::primitives::inline_array::InlineArray;
+#[cfg(feature = "alloc")]
+use crate::{AssembledMessage, Constellation, Encode, FieldWriter, NMEASentenceFormatter};
+use crate::{Fields, ParseError};
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeMap, vec::Vec};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SatelliteInView {
+    pub id: SatelliteId,
+    pub elevation: Option<u8>, // Degrees, 0-90
+    pub azimuth: Option<u16>,  // Degrees true, 0-359
+    pub snr: Option<u8>,       // C/N0 in dB-Hz, null when not tracking
+}
+
+/// The up to four satellites of one `GSV` sentence
+pub type GSVSatellites = InlineArray<SatelliteInView, 4>;
+
+/// GSV - GNSS Satellites in View, one sentence of a group of `total` sentences
+#[derive(Debug, Clone, PartialEq)]
+pub struct GSV {
+    pub total: u8,
+    pub number: u8,
+    pub satellites_in_view: Option<u8>, // Total number of satellites in view of the whole group
+    pub satellites: GSVSatellites,
+    pub signal_id: Option<u8>, // NMEA 4.10 signal id, e.g. 1 for GPS L1 C/A
+}
+
+impl GSV {
+    pub fn from_field(fields: &[&[u8]]) -> Result<Self, ParseError> {
+        if fields.len() < 3 {
+            return Err(ParseError::MissingField(fields.len()));
+        }
+        let total = fields
+            .u8(0)?
+            .filter(|total| *total > 0)
+            .ok_or_else(|| fields.unparsable(0))?;
+        let number = fields
+            .u8(1)?
+            .filter(|number| (1..=total).contains(number))
+            .ok_or_else(|| fields.unparsable(1))?;
+
+        // Four fields per satellite, NMEA 4.10 appends a single signal id field.
+        // Many receivers leave out the SNR field of the last satellite instead of sending it null.
+        let satellite_fields = fields.len() - 3;
+        let (blocks, signal_id) = match satellite_fields % 4 {
+            0 => (satellite_fields / 4, None),
+            1 => (satellite_fields / 4, hex_digit(fields, fields.len() - 1)?),
+            3 => (satellite_fields / 4 + 1, None),
+            _ => return Err(ParseError::MissingField(fields.len())),
+        };
+        let mut satellites = [None; 4];
+        if blocks > satellites.len() {
+            return Err(fields.unparsable(3 + satellites.len() * 4));
+        }
+        for (index, satellite) in satellites.iter_mut().take(blocks).enumerate() {
+            let start = 3 + index * 4;
+            // Some receivers pad the last sentence with null satellites
+            let Some(id) = fields.u16(start)? else {
+                continue;
+            };
+            *satellite = Some(SatelliteInView {
+                id,
+                elevation: fields.u8(start + 1)?,
+                azimuth: fields.u16(start + 2)?,
+                snr: fields.u8(start + 3)?,
+            });
+        }
+
+        Ok(Self {
+            total,
+            number,
+            satellites_in_view: fields.u8(2)?,
+            satellites: satellites.into_iter().flatten().collect(),
+            signal_id,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encode for GSV {
+    fn formatter(&self) -> NMEASentenceFormatter {
+        ['G', 'S', 'V']
+    }
+
+    fn encode_fields(&self, writer: &mut FieldWriter) {
+        writer
+            .uint(Some(self.total), 1)
+            .uint(Some(self.number), 1)
+            .uint(self.satellites_in_view, 2);
+        for satellite in self.satellites.iter() {
+            writer
+                .uint(Some(satellite.id), 2)
+                .uint(satellite.elevation, 2)
+                .uint(satellite.azimuth, 3)
+                .uint(satellite.snr, 2);
+        }
+        if let Some(signal_id) = self.signal_id {
+            writer.hex(Some(signal_id.into()), 1);
+        }
+    }
+}
+
+/// Satellites in view of all constellations, built from complete `GSV` groups.\
+/// Each group replaces what was known about its constellation and signal, so the
+/// view always reflects the latest group of every talker.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+pub struct SkyView {
+    groups: BTreeMap<(Constellation, Option<u8>), Vec<SatelliteInView>>,
+}
+
+#[cfg(feature = "alloc")]
+impl SkyView {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the satellites of `constellation` seen on the signal of the `group`
+    pub fn update(&mut self, constellation: Constellation, group: &[GSV]) {
+        let signal_id = group.first().and_then(|gsv| gsv.signal_id);
+        let satellites = group
+            .iter()
+            .flat_map(|gsv| gsv.satellites.iter().copied())
+            .collect();
+        self.groups.insert((constellation, signal_id), satellites);
+    }
+
+    /// Updates the view from a group put together by a [Reassembler](crate::Reassembler).
+    /// Returns `false` for messages that are not `GSV` or whose talker is no single constellation.
+    pub fn update_message(&mut self, message: &AssembledMessage) -> Result<bool, ParseError> {
+        let Some(constellation) = message.key.talker.constellation() else {
+            return Ok(false);
+        };
+        if message.key.formatter != ['G', 'S', 'V'] {
+            return Ok(false);
+        }
+        let mut group = Vec::with_capacity(message.sentences.len());
+        for sentence in &message.sentences {
+            let sentence = sentence.as_sentence_ref()?;
+            group.push(GSV::from_field(&sentence.fields()?)?);
+        }
+        self.update(constellation, &group);
+        Ok(true)
+    }
+
+    /// Every satellite in view with its constellation and signal id
+    pub fn satellites(
+        &self,
+    ) -> impl Iterator<Item = (Constellation, Option<u8>, &SatelliteInView)> + '_ {
+        self.groups
+            .iter()
+            .flat_map(|((constellation, signal_id), satellites)| {
+                satellites
+                    .iter()
+                    .map(move |satellite| (*constellation, *signal_id, satellite))
+            })
+    }
+
+    /// Number of distinct satellites of `constellation`, a satellite seen on several signals counts once
+    pub fn in_view(&self, constellation: Constellation) -> usize {
+        let mut ids: Vec<SatelliteId> = self
+            .satellites()
+            .filter(|(other, _, _)| *other == constellation)
+            .map(|(_, _, satellite)| satellite.id)
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids.len()
+    }
+
+    /// Mean SNR of the tracked satellites of `constellation`, `None` if none is tracked.
+    /// A satellite reported more than once, e.g. on several signals, counts once with its best SNR.
+    pub fn mean_snr(&self, constellation: Constellation) -> Option<f32> {
+        let mut best: BTreeMap<SatelliteId, u8> = BTreeMap::new();
+        for (_, _, satellite) in self
+            .satellites()
+            .filter(|(other, _, _)| *other == constellation)
+        {
+            if let Some(snr) = satellite.snr {
+                let entry = best.entry(satellite.id).or_insert(snr);
+                *entry = (*entry).max(snr);
+            }
+        }
+        let sum: u32 = best.values().map(|snr| *snr as u32).sum();
+        (!best.is_empty()).then(|| sum as f32 / best.len() as f32)
+    }
+
+    pub fn clear(&mut self) {
+        self.groups.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NMEASentence, Reassembler, Talker};
+    use core::time::Duration;
+
+    #[test]
+    fn test_parse_gsv() {
+        let fields: Vec<&[u8]> = vec![
+            b"3", b"3", b"11", b"22", b"42", b"067", b"42", b"24", b"14", b"311", b"43", b"27",
+            b"05", b"244", b"", b"", b"", b"", b"", b"1",
+        ];
+
+        let gsv = GSV::from_field(&fields).unwrap();
+
+        assert_eq!((gsv.total, gsv.number), (3, 3));
+        assert_eq!(gsv.satellites_in_view, Some(11));
+        assert_eq!(gsv.satellites.len(), 3);
+        assert_eq!(
+            gsv.satellites[2],
+            SatelliteInView {
+                id: 27,
+                elevation: Some(5),
+                azimuth: Some(244),
+                snr: None,
+            }
+        );
+        assert_eq!(gsv.signal_id, Some(1));
+
+        let sentence = gsv.to_sentence(Talker::Gps).unwrap();
+        assert_eq!(
+            sentence.as_bytes(),
+            b"$GPGSV,3,3,11,22,42,067,42,24,14,311,43,27,05,244,,1*50\r\n"
+        );
+
+        let mut invalid = fields.clone();
+        invalid[19] = b"10";
+        assert_eq!(GSV::from_field(&invalid), Err(invalid.unparsable(19)));
+        let mut invalid = fields.clone();
+        invalid[1] = b"4";
+        assert_eq!(GSV::from_field(&invalid), Err(invalid.unparsable(1)));
+        invalid[0] = b"0";
+        assert_eq!(GSV::from_field(&invalid), Err(invalid.unparsable(0)));
+
+        let gsv = GSV::from_field(&fields[..10]).unwrap();
+        assert_eq!(gsv.satellites.len(), 2);
+        assert_eq!(gsv.satellites[1].snr, None);
+        let five_satellites = [&fields[..19], &fields[3..7]].concat();
+        assert_eq!(
+            GSV::from_field(&five_satellites),
+            Err(five_satellites.unparsable(19))
+        );
+    }
+
+    #[test]
+    fn test_sky_view() {
+        let mut reassembler = Reassembler::new();
+        let mut sky_view = SkyView::new();
+        let sentences: [&[u8]; 4] = [
+            b"$GPGSV,2,1,05,01,40,083,46,02,17,308,41,12,07,344,,14,22,228,45*72\r\n",
+            b"$GPGSV,2,2,05,18,12,078,38*42\r\n",
+            b"$GLGSV,1,1,02,65,30,120,35,66,60,250,41*66\r\n",
+            b"$GPGSV,1,1,01,01,40,083,30,8*51\r\n",
+        ];
+        for sentence in sentences {
+            let sentence = NMEASentence::from_bytes(sentence).unwrap();
+            if let Some(message) = reassembler.feed(sentence, Duration::ZERO).unwrap() {
+                assert!(sky_view.update_message(&message).unwrap());
+            }
+        }
+
+        assert_eq!(sky_view.satellites().count(), 8);
+        assert_eq!(sky_view.in_view(Constellation::Gps), 5);
+        assert_eq!(sky_view.mean_snr(Constellation::Gps), Some(42.5));
+        assert_eq!(sky_view.mean_snr(Constellation::Glonass), Some(38.0));
+        assert_eq!(sky_view.mean_snr(Constellation::Galileo), None);
+    }
+}
@@ -1,24 +1,246 @@
 use crate::approved_sentence_formatters::dpt::DPT;
 use crate::approved_sentence_formatters::gga::GGA;
 use crate::approved_sentence_formatters::gsa::GSA;
-use crate::NMEASentenceFormatter;
+use crate::approved_sentence_formatters::gsv::GSV;
+use crate::approved_sentence_formatters::rmc::RMC;
+use crate::approved_sentence_formatters::vtg::VTG;
+#[cfg(feature = "alloc")]
+use crate::encoder::{encode_sentence, Encode, FieldWriter};
+#[cfg(feature = "alloc")]
+use crate::proprietary_sentence_formatters::ProprietaryContent;
+use crate::{Fields, NMEASentenceFormatter, ParseError};
+#[cfg(feature = "alloc")]
+use crate::{NMEASentence, Talker};
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, collections::BTreeMap};
+use core::any::Any;
+use core::fmt;
 
 pub mod dpt;
 pub mod gga;
 pub mod gsa;
+pub mod gsv;
+pub mod rmc;
+pub mod vtg;
 
+/// Satellite id as sent in `GSA` and `GSV`, the numbering depends on the constellation
+pub type SatelliteId = u16;
+
+/// NMEA 4.10 system or signal id, a single hex digit
+fn hex_digit(fields: &[&[u8]], index: usize) -> Result<Option<u8>, ParseError> {
+    match fields.hex(index)? {
+        Some(id) if id > 0xF => Err(fields.unparsable(index)),
+        id => Ok(id.map(|id| id as u8)),
+    }
+}
+
+#[derive(Debug)]
 pub enum SentenceContent {
     DPT(DPT),
     GSA(GSA),
     GGA(GGA),
+    GSV(GSV),
+    RMC(RMC),
+    VTG(VTG),
+    /// Proprietary `$P` sentence, see [ProprietaryRegistry](crate::proprietary_sentence_formatters::ProprietaryRegistry)
+    #[cfg(feature = "alloc")]
+    Proprietary(ProprietaryContent),
+    /// Sentence parsed by a formatter registered with [FormatterRegistry]
+    #[cfg(feature = "alloc")]
+    Custom(Box<dyn CustomSentence>),
     TODO,
 }
 
+/// Typed value of a sentence parsed outside this crate, by a registered [SentenceFormatter]
+/// or a [ProprietaryParser](crate::proprietary_sentence_formatters::ProprietaryParser).\
+/// Implemented by hand so a `Box<dyn CustomSentence>` never passes for a sentence itself:
+/// `as_any` returns `self`.
+pub trait CustomSentence: Any + fmt::Debug + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl dyn CustomSentence {
+    /// The concrete sentence, `None` if it is not a `T`
+    pub fn downcast_ref<T: CustomSentence>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+}
+
+/// Parser for the data fields of one approved sentence.
+pub trait SentenceFormatter: Send + Sync {
+    /// The mnemonic handled, e.g. `GGA`
+    fn formatter(&self) -> NMEASentenceFormatter;
+
+    fn parse(&self, fields: &[&[u8]]) -> Result<SentenceContent, ParseError>;
+
+    /// Writes the data fields of `content`, `false` if the formatter cannot encode it
+    #[cfg(feature = "alloc")]
+    fn encode(&self, _content: &SentenceContent, _writer: &mut FieldWriter) -> bool {
+        false
+    }
+}
+
+/// The formatters implemented by this crate, the fallback of every [FormatterRegistry]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinFormatter {
+    DPT,
+    GGA,
+    GSA,
+    GSV,
+    RMC,
+    VTG,
+}
+
+impl BuiltinFormatter {
+    pub const ALL: [BuiltinFormatter; 6] = [
+        BuiltinFormatter::DPT,
+        BuiltinFormatter::GGA,
+        BuiltinFormatter::GSA,
+        BuiltinFormatter::GSV,
+        BuiltinFormatter::RMC,
+        BuiltinFormatter::VTG,
+    ];
+
+    pub fn from_formatter(formatter: &NMEASentenceFormatter) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|builtin| builtin.formatter() == *formatter)
+    }
+}
+
+impl SentenceFormatter for BuiltinFormatter {
+    fn formatter(&self) -> NMEASentenceFormatter {
+        match self {
+            BuiltinFormatter::DPT => ['D', 'P', 'T'],
+            BuiltinFormatter::GGA => ['G', 'G', 'A'],
+            BuiltinFormatter::GSA => ['G', 'S', 'A'],
+            BuiltinFormatter::GSV => ['G', 'S', 'V'],
+            BuiltinFormatter::RMC => ['R', 'M', 'C'],
+            BuiltinFormatter::VTG => ['V', 'T', 'G'],
+        }
+    }
+
+    fn parse(&self, fields: &[&[u8]]) -> Result<SentenceContent, ParseError> {
+        Ok(match self {
+            BuiltinFormatter::DPT => SentenceContent::DPT(DPT::from_field(fields)?),
+            BuiltinFormatter::GGA => SentenceContent::GGA(GGA::from_field(fields)?),
+            BuiltinFormatter::GSA => SentenceContent::GSA(GSA::from_field(fields)?),
+            BuiltinFormatter::GSV => SentenceContent::GSV(GSV::from_field(fields)?),
+            BuiltinFormatter::RMC => SentenceContent::RMC(RMC::from_field(fields)?),
+            BuiltinFormatter::VTG => SentenceContent::VTG(VTG::from_field(fields)?),
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    fn encode(&self, content: &SentenceContent, writer: &mut FieldWriter) -> bool {
+        match (self, content) {
+            (BuiltinFormatter::DPT, SentenceContent::DPT(dpt)) => dpt.encode_fields(writer),
+            (BuiltinFormatter::GGA, SentenceContent::GGA(gga)) => gga.encode_fields(writer),
+            (BuiltinFormatter::GSA, SentenceContent::GSA(gsa)) => gsa.encode_fields(writer),
+            (BuiltinFormatter::GSV, SentenceContent::GSV(gsv)) => gsv.encode_fields(writer),
+            (BuiltinFormatter::RMC, SentenceContent::RMC(rmc)) => rmc.encode_fields(writer),
+            (BuiltinFormatter::VTG, SentenceContent::VTG(vtg)) => vtg.encode_fields(writer),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Formatters registered at runtime, consulted before the [BuiltinFormatter]s.\
+/// Registering a formatter for a built-in mnemonic replaces the built-in implementation.
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+pub struct FormatterRegistry {
+    formatters: BTreeMap<NMEASentenceFormatter, Box<dyn SentenceFormatter>>,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for FormatterRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.formatters.keys()).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FormatterRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_formatter(mut self, formatter: impl SentenceFormatter + 'static) -> Self {
+        self.register(formatter);
+        self
+    }
+
+    /// Adds `formatter` for its mnemonic, replacing any formatter registered before
+    pub fn register(&mut self, formatter: impl SentenceFormatter + 'static) {
+        self.formatters
+            .insert(formatter.formatter(), Box::new(formatter));
+    }
+
+    /// The registered formatter for `formatter`, falling back to the built-in one
+    pub fn get(&self, formatter: &NMEASentenceFormatter) -> Option<&dyn SentenceFormatter> {
+        match self.formatters.get(formatter) {
+            Some(registered) => Some(registered.as_ref()),
+            None => BuiltinFormatter::ALL
+                .iter()
+                .find(|builtin| builtin.formatter() == *formatter)
+                .map(|builtin| builtin as &dyn SentenceFormatter),
+        }
+    }
+
+    /// Parses the fields of an approved sentence, registered formatters take precedence
+    /// over the [BuiltinFormatter]s. Approved mnemonics without any formatter give
+    /// [SentenceContent::TODO], anything else fails with [ParseError::UnknownFormatter].
+    pub fn select_sentence_formatter(
+        &self,
+        formatter: &NMEASentenceFormatter,
+        fields: &[&[u8]],
+    ) -> Result<SentenceContent, ParseError> {
+        match self.formatters.get(formatter) {
+            Some(registered) => registered.parse(fields),
+            None => select_builtin_formatter(formatter, fields),
+        }
+    }
+
+    /// Encodes `content` with the formatter for `formatter`,
+    /// `None` if there is none or it cannot encode `content`
+    pub fn to_sentence(
+        &self,
+        talker: Talker,
+        formatter: &NMEASentenceFormatter,
+        content: &SentenceContent,
+    ) -> Option<Result<NMEASentence, ParseError>> {
+        let mut writer = FieldWriter::new();
+        if !self.get(formatter)?.encode(content, &mut writer) {
+            return None;
+        }
+        Some(encode_sentence(talker, *formatter, writer.content()))
+    }
+}
+
+/// Parses the fields of an approved sentence with its [BuiltinFormatter].\
+/// Approved mnemonics without an implementation give [SentenceContent::TODO].
+/// Only for builds without `alloc`, otherwise formatters are selected through a `FormatterRegistry`.
+#[cfg_attr(
+    feature = "alloc",
+    deprecated(note = "use FormatterRegistry::select_sentence_formatter")
+)]
 pub fn select_sentence_formatter(
     formatter: &NMEASentenceFormatter,
-    fields: Vec<&[u8]>,
-) -> SentenceContent {
-    match formatter {
+    fields: &[&[u8]],
+) -> Result<SentenceContent, ParseError> {
+    select_builtin_formatter(formatter, fields)
+}
+
+fn select_builtin_formatter(
+    formatter: &NMEASentenceFormatter,
+    fields: &[&[u8]],
+) -> Result<SentenceContent, ParseError> {
+    if let Some(builtin) = BuiltinFormatter::from_formatter(formatter) {
+        return builtin.parse(fields);
+    }
+    Ok(match formatter {
         ['A', 'A', 'M'] => SentenceContent::TODO,
         ['A', 'B', 'K'] => SentenceContent::TODO,
         ['A', 'B', 'M'] => SentenceContent::TODO,
@@ -38,7 +260,6 @@ pub fn select_sentence_formatter(
         ['C', 'U', 'R'] => SentenceContent::TODO,
         ['D', 'B', 'T'] => SentenceContent::TODO,
         ['D', 'C', 'N'] => SentenceContent::TODO,
-        ['D', 'P', 'T'] => SentenceContent::DPT(DPT::from_field(fields)),
         ['D', 'S', 'C'] => SentenceContent::TODO,
         ['D', 'S', 'E'] => SentenceContent::TODO,
         ['D', 'S', 'I'] => SentenceContent::TODO,
@@ -46,15 +267,12 @@ pub fn select_sentence_formatter(
         ['D', 'T', 'M'] => SentenceContent::TODO,
         ['F', 'S', 'I'] => SentenceContent::TODO,
         ['G', 'B', 'S'] => SentenceContent::TODO,
-        ['G', 'G', 'A'] => SentenceContent::GGA(GGA::from_field(fields)),
         ['G', 'L', 'C'] => SentenceContent::TODO,
         ['G', 'L', 'L'] => SentenceContent::TODO,
         ['G', 'M', 'P'] => SentenceContent::TODO,
         ['G', 'N', 'S'] => SentenceContent::TODO,
         ['G', 'R', 'S'] => SentenceContent::TODO,
-        ['G', 'S', 'A'] => SentenceContent::GSA(GSA::from_field(fields)),
         ['G', 'S', 'T'] => SentenceContent::TODO,
-        ['G', 'S', 'V'] => SentenceContent::TODO,
         ['H', 'D', 'G'] => SentenceContent::TODO,
         ['H', 'D', 'T'] => SentenceContent::TODO,
         ['H', 'M', 'R'] => SentenceContent::TODO,
@@ -77,7 +295,6 @@ pub fn select_sentence_formatter(
         ['O', 'S', 'D'] => SentenceContent::TODO,
         ['R', 'M', 'A'] => SentenceContent::TODO,
         ['R', 'M', 'B'] => SentenceContent::TODO,
-        ['R', 'M', 'C'] => SentenceContent::TODO,
         ['R', 'O', 'T'] => SentenceContent::TODO,
         ['R', 'P', 'M'] => SentenceContent::TODO,
         ['R', 'S', 'A'] => SentenceContent::TODO,
@@ -99,7 +316,6 @@ pub fn select_sentence_formatter(
         ['V', 'L', 'W'] => SentenceContent::TODO,
         ['V', 'P', 'W'] => SentenceContent::TODO,
         ['V', 'S', 'D'] => SentenceContent::TODO,
-        ['V', 'T', 'G'] => SentenceContent::TODO,
         ['W', 'C', 'V'] => SentenceContent::TODO,
         ['W', 'N', 'C'] => SentenceContent::TODO,
         ['W', 'P', 'L'] => SentenceContent::TODO,
@@ -110,6 +326,86 @@ pub fn select_sentence_formatter(
         ['Z', 'D', 'L'] => SentenceContent::TODO,
         ['Z', 'F', 'O'] => SentenceContent::TODO,
         ['Z', 'T', 'G'] => SentenceContent::TODO,
-        _ => SentenceContent::TODO,
+        _ => return Err(ParseError::UnknownFormatter(*formatter)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Fields;
+
+    #[derive(Debug, PartialEq)]
+    struct Temperature(Option<f32>);
+
+    impl CustomSentence for Temperature {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    struct WaterTemperature;
+
+    impl SentenceFormatter for WaterTemperature {
+        fn formatter(&self) -> NMEASentenceFormatter {
+            ['M', 'T', 'W']
+        }
+
+        fn parse(&self, fields: &[&[u8]]) -> Result<SentenceContent, ParseError> {
+            Ok(SentenceContent::Custom(Box::new(Temperature(
+                fields.f32(0)?,
+            ))))
+        }
+
+        fn encode(&self, content: &SentenceContent, writer: &mut FieldWriter) -> bool {
+            let SentenceContent::Custom(custom) = content else {
+                return false;
+            };
+            let Some(mtw) = custom.downcast_ref::<Temperature>() else {
+                return false;
+            };
+            writer.float(mtw.0, 1).char(Some('C'));
+            true
+        }
+    }
+
+    #[test]
+    fn test_formatter_registry() {
+        let fields: &[&[u8]] = &[b"17.5", b"C"];
+        let mut registry = FormatterRegistry::new();
+        assert!(matches!(
+            registry.select_sentence_formatter(&['M', 'T', 'W'], fields),
+            Ok(SentenceContent::TODO)
+        ));
+
+        registry.register(WaterTemperature);
+        let content = registry
+            .select_sentence_formatter(&['M', 'T', 'W'], fields)
+            .unwrap();
+        let SentenceContent::Custom(custom) = &content else {
+            panic!("MTW not parsed by the registered formatter");
+        };
+        assert_eq!(
+            custom.downcast_ref::<Temperature>(),
+            Some(&Temperature(Some(17.5)))
+        );
+        assert_eq!(
+            (**custom).as_any().downcast_ref::<Temperature>(),
+            Some(&Temperature(Some(17.5)))
+        );
+
+        let sentence = registry
+            .to_sentence(Talker::Sounder, &['M', 'T', 'W'], &content)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sentence.as_bytes(), b"$SDMTW,17.5,C*07\r\n");
+
+        let dpt = registry
+            .select_sentence_formatter(&['D', 'P', 'T'], &[b"87.4", b"0.0"])
+            .unwrap();
+        assert!(matches!(dpt, SentenceContent::DPT(_)));
+        assert!(registry
+            .to_sentence(Talker::Sounder, &['G', 'G', 'A'], &dpt)
+            .is_none());
     }
 }
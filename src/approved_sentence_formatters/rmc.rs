@@ -0,0 +1,268 @@
+use crate::primitives::coordinates::Coordinate;
+#[cfg(feature = "alloc")]
+use crate::{Encode, FieldWriter, NMEASentenceFormatter, ToField};
+use crate::{Fields, FromField, ParseError};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RMCStatus {
+    Valid,   // A
+    Invalid, // V, navigation receiver warning
+}
+
+impl FromField for RMCStatus {
+    fn from_field(field: &[u8]) -> Option<Self> {
+        match field {
+            b"A" => Some(RMCStatus::Valid),
+            b"V" => Some(RMCStatus::Invalid),
+            _ => None,
+        }
+    }
+}
+
+/// Positioning system mode indicator, NMEA 2.3 and later
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ModeIndicator {
+    Autonomous,   // A
+    Differential, // D
+    Estimated,    // E, dead reckoning
+    FloatRTK,     // F
+    Manual,       // M, manual input
+    NotValid,     // N, data not valid
+    Precise,      // P
+    RTK,          // R, real time kinematic with fixed integers
+    Simulator,    // S
+}
+
+impl FromField for ModeIndicator {
+    fn from_field(field: &[u8]) -> Option<Self> {
+        match field {
+            b"A" => Some(ModeIndicator::Autonomous),
+            b"D" => Some(ModeIndicator::Differential),
+            b"E" => Some(ModeIndicator::Estimated),
+            b"F" => Some(ModeIndicator::FloatRTK),
+            b"M" => Some(ModeIndicator::Manual),
+            b"N" => Some(ModeIndicator::NotValid),
+            b"P" => Some(ModeIndicator::Precise),
+            b"R" => Some(ModeIndicator::RTK),
+            b"S" => Some(ModeIndicator::Simulator),
+            _ => None,
+        }
+    }
+}
+
+/// Navigational status, NMEA 4.1 and later
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NavigationalStatus {
+    Safe,     // S
+    Caution,  // C
+    Unsafe,   // U
+    NotValid, // V, equipment is not providing navigational status
+}
+
+impl FromField for NavigationalStatus {
+    fn from_field(field: &[u8]) -> Option<Self> {
+        match field {
+            b"S" => Some(NavigationalStatus::Safe),
+            b"C" => Some(NavigationalStatus::Caution),
+            b"U" => Some(NavigationalStatus::Unsafe),
+            b"V" => Some(NavigationalStatus::NotValid),
+            _ => None,
+        }
+    }
+}
+
+/// RMC - Recommended Minimum Specific GNSS Data
+#[derive(Debug, Clone, PartialEq)]
+pub struct RMC {
+    pub time: Option<NaiveTime>, // UTC of position fix
+    pub status: Option<RMCStatus>,
+    pub latitude: Option<Coordinate>,
+    pub longitude: Option<Coordinate>,
+    pub speed_over_ground: Option<f32>,  // Knots
+    pub course_over_ground: Option<f32>, // Degrees true
+    pub date: Option<NaiveDate>,
+    pub magnetic_variation: Option<f32>, // Degrees, easterly positive, westerly negative
+    pub mode: Option<ModeIndicator>,
+    pub navigational_status: Option<NavigationalStatus>,
+}
+
+impl RMC {
+    pub fn from_field(fields: &[&[u8]]) -> Result<Self, ParseError> {
+        // Mode indicator and navigational status were added in NMEA 2.3 and 4.1
+        if fields.len() < 11 {
+            return Err(ParseError::MissingField(fields.len()));
+        }
+        let magnetic_variation = match (fields.f32(9)?, fields.char(10)?) {
+            (Some(variation), Some('E')) => Some(variation),
+            (Some(variation), Some('W')) => Some(-variation),
+            (Some(_), _) => return Err(fields.unparsable(10)),
+            (None, _) => None,
+        };
+
+        Ok(Self {
+            time: fields.time(0)?,
+            status: fields.enumeration(1)?,
+            latitude: fields.latitude(2)?,
+            longitude: fields.longitude(4)?,
+            speed_over_ground: fields.f32(6)?,
+            course_over_ground: fields.f32(7)?,
+            date: fields.date(8)?,
+            magnetic_variation,
+            mode: fields.enumeration(11)?,
+            navigational_status: fields.enumeration(12)?,
+        })
+    }
+
+    /// UTC date and time of the fix, `None` if either field is null
+    pub fn date_time(&self) -> Option<DateTime<Utc>> {
+        Some(self.date?.and_time(self.time?).and_utc())
+    }
+
+    /// The receiver reports valid data and, if present, a mode other than [ModeIndicator::NotValid]
+    pub fn is_valid(&self) -> bool {
+        self.status == Some(RMCStatus::Valid) && self.mode != Some(ModeIndicator::NotValid)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToField for RMCStatus {
+    fn to_field(&self) -> &'static str {
+        match self {
+            RMCStatus::Valid => "A",
+            RMCStatus::Invalid => "V",
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToField for ModeIndicator {
+    fn to_field(&self) -> &'static str {
+        match self {
+            ModeIndicator::Autonomous => "A",
+            ModeIndicator::Differential => "D",
+            ModeIndicator::Estimated => "E",
+            ModeIndicator::FloatRTK => "F",
+            ModeIndicator::Manual => "M",
+            ModeIndicator::NotValid => "N",
+            ModeIndicator::Precise => "P",
+            ModeIndicator::RTK => "R",
+            ModeIndicator::Simulator => "S",
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToField for NavigationalStatus {
+    fn to_field(&self) -> &'static str {
+        match self {
+            NavigationalStatus::Safe => "S",
+            NavigationalStatus::Caution => "C",
+            NavigationalStatus::Unsafe => "U",
+            NavigationalStatus::NotValid => "V",
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encode for RMC {
+    fn formatter(&self) -> NMEASentenceFormatter {
+        ['R', 'M', 'C']
+    }
+
+    fn encode_fields(&self, writer: &mut FieldWriter) {
+        writer
+            .time(self.time)
+            .enumeration(self.status.as_ref())
+            .latitude(self.latitude)
+            .longitude(self.longitude)
+            .float(self.speed_over_ground, 1)
+            .float(self.course_over_ground, 1)
+            .date(self.date)
+            .float(self.magnetic_variation.map(f32::abs), 1)
+            .char(
+                self.magnetic_variation
+                    .map(|variation| if variation < 0.0 { 'W' } else { 'E' }),
+            )
+            .enumeration(self.mode.as_ref())
+            .enumeration(self.navigational_status.as_ref());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Talker;
+    use chrono::NaiveDateTime;
+
+    #[test]
+    fn test_parse_rmc() {
+        let fields: Vec<&[u8]> = vec![
+            b"123519",
+            b"A",
+            b"4807.038",
+            b"N",
+            b"01131.000",
+            b"E",
+            b"022.4",
+            b"084.4",
+            b"230394",
+            b"003.1",
+            b"W",
+        ];
+
+        let rmc = RMC::from_field(&fields).unwrap();
+
+        assert_eq!(rmc.status, Some(RMCStatus::Valid));
+        assert_eq!(rmc.latitude.unwrap().degrees(), 48);
+        assert_eq!(rmc.speed_over_ground, Some(22.4));
+        assert_eq!(rmc.course_over_ground, Some(84.4));
+        assert_eq!(rmc.magnetic_variation, Some(-3.1));
+        assert_eq!(rmc.mode, None);
+        assert!(rmc.is_valid());
+        assert_eq!(
+            rmc.date_time().map(|date_time| date_time.naive_utc()),
+            NaiveDateTime::parse_from_str("1994-03-23 12:35:19", "%Y-%m-%d %H:%M:%S").ok()
+        );
+
+        let mut fields = fields;
+        fields[10] = b"";
+        assert_eq!(
+            RMC::from_field(&fields).unwrap_err(),
+            ParseError::UnparsableField {
+                index: 10,
+                offset: 57
+            }
+        );
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let fields: Vec<&[u8]> = vec![
+            b"123519.50",
+            b"A",
+            b"4807.038",
+            b"N",
+            b"01131.000",
+            b"E",
+            b"022.4",
+            b"084.4",
+            b"230394",
+            b"003.1",
+            b"E",
+            b"D",
+            b"S",
+        ];
+        let rmc = RMC::from_field(&fields).unwrap();
+        assert_eq!(rmc.navigational_status, Some(NavigationalStatus::Safe));
+
+        let sentence = rmc.to_sentence(Talker::Gps).unwrap();
+        assert_eq!(
+            sentence.as_bytes(),
+            b"$GPRMC,123519.50,A,4807.0380,N,01131.0000,E,22.4,84.4,230394,3.1,E,D,S*44\r\n"
+        );
+
+        let content = sentence.decode().unwrap();
+        assert_eq!(RMC::from_field(&content.fields().unwrap()).unwrap(), rmc);
+    }
+}
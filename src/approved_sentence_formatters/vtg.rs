@@ -0,0 +1,125 @@
+use crate::approved_sentence_formatters::rmc::ModeIndicator;
+#[cfg(feature = "alloc")]
+use crate::{Encode, FieldWriter, NMEASentenceFormatter};
+use crate::{Fields, ParseError};
+
+/// VTG - Course Over Ground and Ground Speed
+#[derive(Debug, Clone, PartialEq)]
+pub struct VTG {
+    pub course_true: Option<f32>,     // Degrees true
+    pub course_magnetic: Option<f32>, // Degrees magnetic
+    pub speed_knots: Option<f32>,
+    pub speed_kmh: Option<f32>,
+    pub mode: Option<ModeIndicator>, // NMEA 2.3 and later
+}
+
+impl VTG {
+    pub fn from_field(fields: &[&[u8]]) -> Result<Self, ParseError> {
+        match fields.len() {
+            // Before NMEA 2.0 the values were sent without unit fields
+            4 => Ok(Self {
+                course_true: fields.f32(0)?,
+                course_magnetic: fields.f32(1)?,
+                speed_knots: fields.f32(2)?,
+                speed_kmh: fields.f32(3)?,
+                mode: None,
+            }),
+            length if length >= 8 => {
+                for (index, unit) in [(1, 'T'), (3, 'M'), (5, 'N'), (7, 'K')] {
+                    if fields.char(index)?.is_some_and(|field| field != unit) {
+                        return Err(fields.unparsable(index));
+                    }
+                }
+                Ok(Self {
+                    course_true: fields.f32(0)?,
+                    course_magnetic: fields.f32(2)?,
+                    speed_knots: fields.f32(4)?,
+                    speed_kmh: fields.f32(6)?,
+                    mode: fields.enumeration(8)?,
+                })
+            }
+            length => Err(ParseError::MissingField(length)),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encode for VTG {
+    fn formatter(&self) -> NMEASentenceFormatter {
+        ['V', 'T', 'G']
+    }
+
+    fn encode_fields(&self, writer: &mut FieldWriter) {
+        writer
+            .float(self.course_true, 1)
+            .char(Some('T'))
+            .float(self.course_magnetic, 1)
+            .char(Some('M'))
+            .float(self.speed_knots, 1)
+            .char(Some('N'))
+            .float(self.speed_kmh, 1)
+            .char(Some('K'))
+            .enumeration(self.mode.as_ref());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Talker;
+
+    #[test]
+    fn test_parse_vtg() {
+        let fields: Vec<&[u8]> = vec![
+            b"054.7", b"T", b"034.4", b"M", b"005.5", b"N", b"010.2", b"K", b"A",
+        ];
+        let vtg = VTG::from_field(&fields).unwrap();
+        assert_eq!(
+            vtg,
+            VTG {
+                course_true: Some(54.7),
+                course_magnetic: Some(34.4),
+                speed_knots: Some(5.5),
+                speed_kmh: Some(10.2),
+                mode: Some(ModeIndicator::Autonomous),
+            }
+        );
+
+        let legacy: Vec<&[u8]> = vec![b"054.7", b"034.4", b"005.5", b"010.2"];
+        assert_eq!(VTG::from_field(&legacy).unwrap(), VTG { mode: None, ..vtg });
+
+        let mut fields = fields;
+        fields[5] = b"K";
+        assert_eq!(
+            VTG::from_field(&fields).unwrap_err(),
+            ParseError::UnparsableField {
+                index: 5,
+                offset: 22
+            }
+        );
+        assert_eq!(
+            VTG::from_field(&fields[..6]).unwrap_err(),
+            ParseError::MissingField(6)
+        );
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let vtg = VTG {
+            course_true: Some(54.7),
+            course_magnetic: None,
+            speed_knots: Some(5.5),
+            speed_kmh: Some(10.2),
+            mode: Some(ModeIndicator::Differential),
+        };
+
+        let sentence = vtg.to_sentence(Talker::Gps).unwrap();
+        assert_eq!(
+            sentence.as_bytes(),
+            b"$GPVTG,54.7,T,,M,5.5,N,10.2,K,D*0D\r\n"
+        );
+
+        let content = sentence.decode().unwrap();
+        assert_eq!(VTG::from_field(&content.fields().unwrap()).unwrap(), vtg);
+    }
+}
@@ -0,0 +1,233 @@
+use crate::decoder::{DecodeEvent, FramingPolicy, FramingStats, NMEADecoder};
+use crate::sentence::{Address, ChecksumPolicy, NMEASentence, NMEASentenceFormatter};
+use memmap2::Mmap;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+const DEFAULT_CHUNK_SIZE: usize = 1 << 20;
+
+/// Maps a recording into memory for [BulkParser].
+///
+/// # Safety
+/// The mapping is only valid as long as no other process truncates or modifies the file.
+pub unsafe fn map_file<P: AsRef<Path>>(path: P) -> io::Result<Mmap> {
+    let file = File::open(path)?;
+    Mmap::map(&file)
+}
+
+/// Decodes large in-memory recordings on all cores.\
+/// The input is cut into chunks where a line ends right before a start delimiter, each chunk
+/// gets its own [NMEADecoder] and the results are put back together in their original order.
+/// A single decoder is in the same state at those points, so the events do not depend on the chunk size.
+#[derive(Debug, Clone, Copy)]
+pub struct BulkParser {
+    framing: FramingPolicy,
+    checksum_policy: ChecksumPolicy,
+    max_length: Option<usize>,
+    chunk_size: usize,
+}
+
+impl Default for BulkParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BulkParser {
+    pub fn new() -> Self {
+        BulkParser {
+            framing: FramingPolicy::default(),
+            checksum_policy: ChecksumPolicy::default(),
+            max_length: None,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    pub fn with_framing(mut self, framing: FramingPolicy) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    pub fn with_checksum_policy(mut self, checksum_policy: ChecksumPolicy) -> Self {
+        self.checksum_policy = checksum_policy;
+        self
+    }
+
+    /// See [NMEADecoder::with_max_length]
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Approximate number of bytes handed to one worker at a time, 1 MiB by default
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    fn decoder(&self) -> NMEADecoder {
+        let decoder = NMEADecoder::new()
+            .with_framing(self.framing)
+            .with_checksum_policy(self.checksum_policy);
+        match self.max_length {
+            Some(max_length) => decoder.with_max_length(max_length),
+            None => decoder,
+        }
+    }
+
+    fn decode_chunk(&self, chunk: &[u8]) -> (Vec<DecodeEvent>, FramingStats) {
+        let mut decoder = self.decoder();
+        let mut events = decoder.feed(chunk);
+        events.extend(decoder.finish());
+        (events, decoder.framing_stats())
+    }
+
+    /// Cuts `data` into chunks of about `chunk_size` bytes. A chunk ends after a `\n` followed by
+    /// `$`, `!` or `\`: the decoder is idle there and would flush any garbage anyway.
+    fn chunks<'a>(&self, data: &'a [u8]) -> Vec<&'a [u8]> {
+        let mut chunks = vec![];
+        let mut start = 0;
+        while start < data.len() {
+            let mut end = (start + self.chunk_size).min(data.len());
+            while end < data.len()
+                && !(data[end - 1] == b'\n' && matches!(data[end], b'$' | b'!' | b'\\'))
+            {
+                end += 1;
+            }
+            chunks.push(&data[start..end]);
+            start = end;
+        }
+        chunks
+    }
+
+    /// Every [DecodeEvent] of `data` as a parallel iterator, only the chunks being worked on
+    /// are held in memory. Collecting it gives the events a single [NMEADecoder] would produce.
+    pub fn par_events<'a>(
+        &'a self,
+        data: &'a [u8],
+    ) -> impl ParallelIterator<Item = DecodeEvent> + 'a {
+        self.chunks(data)
+            .into_par_iter()
+            .flat_map_iter(|chunk| self.decode_chunk(chunk).0)
+    }
+
+    /// The sentences of [BulkParser::par_events], e.g. to `fold` and `reduce` a recording
+    /// without collecting it
+    pub fn par_sentences<'a>(
+        &'a self,
+        data: &'a [u8],
+    ) -> impl ParallelIterator<Item = NMEASentence> + 'a {
+        self.par_events(data).filter_map(|event| match event {
+            DecodeEvent::Sentence(sentence) => Some(sentence),
+            DecodeEvent::Discarded { .. } => None,
+        })
+    }
+
+    /// Every [DecodeEvent] of `data` in the order a single [NMEADecoder] would produce them.
+    pub fn decode(&self, data: &[u8]) -> Vec<DecodeEvent> {
+        self.par_events(data).collect()
+    }
+
+    /// Applies `map` to every sentence in parallel, the results keep the order of the input.
+    /// Sentences for which `map` returns `None` are left out.
+    pub fn filter_map<T, F>(&self, data: &[u8], map: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(NMEASentence) -> Option<T> + Sync + Send,
+    {
+        self.par_sentences(data).filter_map(map).collect()
+    }
+
+    /// The [FramingStats] of all chunks added up, the same a single [NMEADecoder] reports for `data`
+    pub fn framing_stats(&self, data: &[u8]) -> FramingStats {
+        self.chunks(data)
+            .into_par_iter()
+            .map(|chunk| self.decode_chunk(chunk).1)
+            .reduce(FramingStats::default, |total, stats| FramingStats {
+                lf_only: total.lf_only + stats.lf_only,
+                cr_only: total.cr_only + stats.cr_only,
+                unterminated_eof: total.unterminated_eof + stats.unterminated_eof,
+                oversize: total.oversize + stats.oversize,
+            })
+    }
+
+    /// Number of approved sentences per formatter, e.g. how many `GGA` a recording holds
+    pub fn formatter_counts(&self, data: &[u8]) -> HashMap<NMEASentenceFormatter, usize> {
+        self.par_sentences(data)
+            .fold(HashMap::new, |mut counts, sentence| {
+                if let Ok(sentence) = sentence.as_sentence_ref() {
+                    if let Address::Approved(address) = sentence.address().address {
+                        *counts.entry(address.formatter).or_insert(0) += 1;
+                    }
+                }
+                counts
+            })
+            .reduce(HashMap::new, |mut total, counts| {
+                for (formatter, count) in counts {
+                    *total.entry(formatter).or_insert(0) += count;
+                }
+                total
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulk_decode_in_order() {
+        let data = b"$SDDPT,87.4,0.0,*40\r\n$SDDPT,87.5,0.0,*41\r\nnoise\r\n$GPGSA,A,3,32,21,,,,,,,,,,,1.50,0.90,1.20*0E\r\n$SDDPT,87.6,0.0,*42\r\n".repeat(50);
+        let parser = BulkParser::new().with_chunk_size(30);
+
+        let mut decoder = NMEADecoder::new();
+        let mut expected = decoder.feed(&data);
+        expected.extend(decoder.finish());
+
+        let events = parser.decode(&data);
+        assert_eq!(format!("{:?}", events), format!("{:?}", expected));
+
+        let lengths = parser.filter_map(&data, |sentence| Some(sentence.len()));
+        assert_eq!(&lengths[..4], &[21, 21, 46, 21]);
+
+        let bytes = parser
+            .par_sentences(&data)
+            .map(|sentence| sentence.len())
+            .sum::<usize>();
+        assert_eq!(bytes, 50 * (21 * 3 + 46));
+
+        let counts = parser.formatter_counts(&data);
+        assert_eq!(counts[&['D', 'P', 'T']], 150);
+        assert_eq!(counts[&['G', 'S', 'A']], 50);
+    }
+
+    #[test]
+    fn test_bulk_decode_matches_decoder() {
+        let data =
+            b"$SDDPT,87.4,0.0,*40\rnoise\r\n\r\n$SDDPT,87.5,0.0,*41\n\r$SDDPT,87.6\n\\s:x*31\\\r\n\
+garbage\nmore garbage\n$SDDPT,87.6,0.0,*42\r\n!AIVDM,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,0*5C\n\
+\\s:r3669961,c:1503079517*70\\$SDDPT,87.4,0.0,*40\r\n$SDDPT,87.7,0.0,*43\r";
+
+        for framing in [FramingPolicy::STRICT, FramingPolicy::LENIENT] {
+            let mut decoder = NMEADecoder::new().with_framing(framing);
+            let mut expected = decoder.feed(data);
+            expected.extend(decoder.finish());
+
+            for chunk_size in 1..data.len() {
+                let parser = BulkParser::new()
+                    .with_framing(framing)
+                    .with_chunk_size(chunk_size);
+                assert_eq!(
+                    format!("{:?}", parser.decode(data)),
+                    format!("{:?}", expected),
+                    "chunk size {}",
+                    chunk_size
+                );
+                assert_eq!(parser.framing_stats(data), decoder.framing_stats());
+            }
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::decoder::{DecodeEvent, FramingPolicy, FramingStats, NMEADecoder};
+use crate::sentence::{ChecksumPolicy, NMEASentence};
+use bytes::{BufMut, BytesMut};
+use futures_core::Stream;
+use std::collections::VecDeque;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::AsyncRead;
+use tokio_util::codec::{Decoder, Encoder, FramedRead};
+
+/// [tokio_util] codec around [NMEADecoder], for `Framed` sockets and serial ports.\
+/// Decoding yields every [DecodeEvent], encoding writes a sentence with its tag block.
+#[derive(Debug, Default)]
+pub struct NMEACodec {
+    decoder: NMEADecoder,
+    events: VecDeque<DecodeEvent>,
+}
+
+impl NMEACodec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses an already configured decoder
+    pub fn with_decoder(decoder: NMEADecoder) -> Self {
+        NMEACodec {
+            decoder,
+            events: VecDeque::new(),
+        }
+    }
+
+    pub fn decoder(&self) -> &NMEADecoder {
+        &self.decoder
+    }
+}
+
+impl Decoder for NMEACodec {
+    type Item = DecodeEvent;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if self.events.is_empty() && !src.is_empty() {
+            let data = src.split();
+            self.events.extend(self.decoder.feed(&data));
+        }
+        Ok(self.events.pop_front())
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if let Some(event) = self.decode(src)? {
+            return Ok(Some(event));
+        }
+        self.events.extend(self.decoder.finish());
+        Ok(self.events.pop_front())
+    }
+}
+
+impl Encoder<&NMEASentence> for NMEACodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, sentence: &NMEASentence, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        sentence.write_to(&mut dst.writer())
+    }
+}
+
+impl Encoder<NMEASentence> for NMEACodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, sentence: NMEASentence, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.encode(&sentence, dst)
+    }
+}
+
+/// Async counterpart of [NMEASentenceReader](crate::NMEASentenceReader), a [Stream] of the
+/// sentences read from any [AsyncRead]. Discarded bytes are skipped, use
+/// [AsyncNMEASentenceReader::into_inner] and [NMEACodec] directly to observe them.
+pub struct AsyncNMEASentenceReader<ReaderType: AsyncRead + Unpin> {
+    framed: FramedRead<ReaderType, NMEACodec>,
+}
+
+impl<ReaderType: AsyncRead + Unpin> AsyncNMEASentenceReader<ReaderType> {
+    pub fn new(reader: ReaderType) -> Self {
+        AsyncNMEASentenceReader {
+            framed: FramedRead::new(reader, NMEACodec::new()),
+        }
+    }
+
+    fn map_decoder(mut self, configure: impl FnOnce(NMEADecoder) -> NMEADecoder) -> Self {
+        let codec = self.framed.decoder_mut();
+        codec.decoder = configure(std::mem::take(&mut codec.decoder));
+        self
+    }
+
+    pub fn with_framing(self, framing: FramingPolicy) -> Self {
+        self.map_decoder(|decoder| decoder.with_framing(framing))
+    }
+
+    pub fn with_checksum_policy(self, checksum_policy: ChecksumPolicy) -> Self {
+        self.map_decoder(|decoder| decoder.with_checksum_policy(checksum_policy))
+    }
+
+    /// See [NMEADecoder::with_max_length]
+    pub fn with_max_length(self, max_length: usize) -> Self {
+        self.map_decoder(|decoder| decoder.with_max_length(max_length))
+    }
+
+    pub fn framing_stats(&self) -> FramingStats {
+        self.framed.decoder().decoder.framing_stats()
+    }
+
+    pub fn decoder(&self) -> &NMEADecoder {
+        &self.framed.decoder().decoder
+    }
+
+    pub fn into_inner(self) -> FramedRead<ReaderType, NMEACodec> {
+        self.framed
+    }
+}
+
+impl<ReaderType: AsyncRead + Unpin> Stream for AsyncNMEASentenceReader<ReaderType> {
+    type Item = io::Result<NMEASentence>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match Pin::new(&mut self.framed).poll_next(cx) {
+                Poll::Ready(Some(Ok(DecodeEvent::Sentence(sentence)))) => {
+                    return Poll::Ready(Some(Ok(sentence)))
+                }
+                Poll::Ready(Some(Ok(DecodeEvent::Discarded { .. }))) => {}
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_stream::StreamExt;
+
+    #[tokio::test]
+    async fn test_async_reader() {
+        let data: &[u8] = b"$SDDPT,87.4,0.0,*40\r\nnoise$SDDPT,87.5,0.0,*41\n$SDDPT,87.6,0.0,*42";
+        let reader = AsyncNMEASentenceReader::new(data).with_framing(FramingPolicy::LENIENT);
+
+        let sentences = reader
+            .map(|sentence| sentence.unwrap().as_bytes().to_vec())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            sentences,
+            vec![
+                b"$SDDPT,87.4,0.0,*40\r\n".to_vec(),
+                b"$SDDPT,87.5,0.0,*41\r\n".to_vec(),
+                b"$SDDPT,87.6,0.0,*42\r\n".to_vec(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_codec_round_trip() {
+        let mut codec = NMEACodec::new();
+        let sentence = NMEASentence::from_bytes(b"$SDDPT,87.4,0.0,*40\r\n").unwrap();
+
+        let mut buffer = BytesMut::new();
+        codec.encode(&sentence, &mut buffer).unwrap();
+        buffer.extend_from_slice(b"$SDDPT,87");
+
+        let Some(DecodeEvent::Sentence(decoded)) = codec.decode(&mut buffer).unwrap() else {
+            panic!("expected a sentence");
+        };
+        assert_eq!(decoded.as_bytes(), sentence.as_bytes());
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+        assert!(matches!(
+            codec.decode_eof(&mut buffer).unwrap(),
+            Some(DecodeEvent::Discarded { bytes: 9, .. })
+        ));
+    }
+}
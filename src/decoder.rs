@@ -0,0 +1,613 @@
+use crate::error::ParseError;
+use crate::sentence::{ChecksumPolicy, NMEASentence, NMEA_SENTENCE_MAX_LENGTH};
+use crate::tag_block::{TagBlock, TAG_BLOCK_MAX_LENGTH};
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Debug)]
+#[allow(clippy::upper_case_acronyms)]
+enum SentenceStatus {
+    NONE,
+    TAG,
+    STARTED,
+    TERMINATED,
+    /// Skipping the rest of a sentence longer than the maximum length
+    OVERSIZE,
+}
+
+/// Why the [NMEADecoder] dropped bytes from the stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscardReason {
+    /// Bytes outside of a sentence, before the next `$` or `!`
+    NoStartDelimiter,
+    /// A new start delimiter arrived before the previous sentence was terminated
+    Interrupted,
+    /// The sentence was longer than [NMEADecoder::max_length], the whole sentence up to its terminator is dropped
+    Oversize,
+    /// The sentence was not terminated by a line ending accepted by the [FramingPolicy]
+    BadTerminator,
+    /// The input ended in the middle of a sentence
+    Truncated,
+    /// A tag block was malformed or its checksum did not match
+    BadTagBlock,
+    /// The checksum did not match or was malformed
+    BadChecksum,
+    /// The sentence had no checksum although the [ChecksumPolicy] requires one
+    MissingChecksum,
+}
+
+/// Line terminations accepted by the [NMEADecoder] on top of the `\r\n` pair
+/// required by IEC 61162-1. Rescued sentences are always stored with `\r\n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FramingPolicy {
+    /// Accept a bare `\n`, this also covers devices sending `\n\r`
+    pub accept_lf: bool,
+    /// Accept a bare `\r`
+    pub accept_cr: bool,
+    /// Accept a sentence without any terminator at the end of the input
+    pub accept_unterminated_eof: bool,
+}
+
+impl FramingPolicy {
+    pub const STRICT: FramingPolicy = FramingPolicy {
+        accept_lf: false,
+        accept_cr: false,
+        accept_unterminated_eof: false,
+    };
+    pub const LENIENT: FramingPolicy = FramingPolicy {
+        accept_lf: true,
+        accept_cr: true,
+        accept_unterminated_eof: true,
+    };
+}
+
+impl Default for FramingPolicy {
+    fn default() -> Self {
+        FramingPolicy::STRICT
+    }
+}
+
+/// Number of sentences rescued by each of the lenient [FramingPolicy] options
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FramingStats {
+    pub lf_only: usize,
+    pub cr_only: usize,
+    pub unterminated_eof: usize,
+    /// Sentences longer than [NMEA_SENTENCE_MAX_LENGTH] accepted because of a raised [NMEADecoder::max_length]
+    pub oversize: usize,
+}
+
+#[derive(Debug, Clone)]
+pub enum DecodeEvent {
+    Sentence(NMEASentence),
+    Discarded { bytes: usize, reason: DiscardReason },
+}
+
+/// Push based sentence framer, bytes can be fed in arbitrary chunks and partial
+/// sentences are kept until the next call to [NMEADecoder::feed].
+/// A tag block directly in front of a sentence is parsed and attached to it.
+#[derive(Debug)]
+pub struct NMEADecoder {
+    status: SentenceStatus,
+    sentence: Vec<u8>,
+    length: usize,
+    max_length: usize,
+    tag: Vec<u8>,
+    tag_block: Option<Box<TagBlock>>,
+    garbage: usize,
+    /// The last sentence ended with a bare `\n`, a `\r` right after it belongs to the terminator
+    after_lf: bool,
+    discarded: usize,
+    framing: FramingPolicy,
+    framing_stats: FramingStats,
+    checksum_policy: ChecksumPolicy,
+}
+
+impl Default for NMEADecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NMEADecoder {
+    pub fn new() -> Self {
+        NMEADecoder {
+            status: SentenceStatus::NONE,
+            sentence: Vec::with_capacity(NMEA_SENTENCE_MAX_LENGTH),
+            length: 0,
+            max_length: NMEA_SENTENCE_MAX_LENGTH,
+            tag: vec![],
+            tag_block: None,
+            garbage: 0,
+            after_lf: false,
+            discarded: 0,
+            framing: FramingPolicy::STRICT,
+            framing_stats: FramingStats::default(),
+            checksum_policy: ChecksumPolicy::default(),
+        }
+    }
+
+    pub fn with_checksum_policy(mut self, checksum_policy: ChecksumPolicy) -> Self {
+        self.checksum_policy = checksum_policy;
+        self
+    }
+
+    pub fn checksum_policy(&self) -> ChecksumPolicy {
+        self.checksum_policy
+    }
+
+    pub fn with_framing(mut self, framing: FramingPolicy) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    pub fn framing(&self) -> FramingPolicy {
+        self.framing
+    }
+
+    /// Longest sentence accepted, `\r\n` included. Defaults to [NMEA_SENTENCE_MAX_LENGTH],
+    /// some receivers need more for messages like `PUBX,00` or high precision `GGA`.
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    pub fn framing_stats(&self) -> FramingStats {
+        self.framing_stats
+    }
+
+    /// Total number of bytes dropped since the decoder was created
+    pub fn discarded(&self) -> usize {
+        self.discarded
+    }
+
+    pub fn feed(&mut self, data: &[u8]) -> Vec<DecodeEvent> {
+        let mut events = vec![];
+        for byte in data.iter() {
+            self.feed_byte(*byte, &mut events);
+        }
+        self.flush_garbage(&mut events);
+        events
+    }
+
+    /// Signals the end of the input, resolving a sentence that is still waiting for its terminator.
+    pub fn finish(&mut self) -> Vec<DecodeEvent> {
+        let mut events = vec![];
+        match self.status {
+            SentenceStatus::NONE => {
+                if self.tag_block.is_some() {
+                    self.discard(&mut events, 0, DiscardReason::Truncated);
+                }
+            }
+            SentenceStatus::TAG => self.discard(&mut events, 0, DiscardReason::Truncated),
+            SentenceStatus::STARTED if self.framing.accept_unterminated_eof => {
+                self.framing_stats.unterminated_eof += 1;
+                self.complete(&mut events, 0);
+            }
+            SentenceStatus::TERMINATED if self.framing.accept_cr => {
+                self.framing_stats.cr_only += 1;
+                self.complete(&mut events, 1);
+            }
+            SentenceStatus::TERMINATED => {
+                self.discard(&mut events, self.length + 1, DiscardReason::BadTerminator)
+            }
+            SentenceStatus::OVERSIZE => {
+                self.discard(&mut events, self.length, DiscardReason::Oversize)
+            }
+            _ => self.discard(&mut events, self.length, DiscardReason::Truncated),
+        }
+        self.flush_garbage(&mut events);
+        events
+    }
+
+    fn feed_byte(&mut self, byte: u8, events: &mut Vec<DecodeEvent>) {
+        if core::mem::take(&mut self.after_lf) && byte == b'\r' {
+            return;
+        }
+        match self.status {
+            SentenceStatus::NONE => {
+                if byte == b'$' || byte == b'!' {
+                    self.flush_garbage(events);
+                    self.start(byte);
+                } else if byte == b'\\' {
+                    self.flush_garbage(events);
+                    if self.tag_block.is_some() {
+                        self.discard(events, 0, DiscardReason::Interrupted);
+                    }
+                    self.start_tag();
+                } else {
+                    // A line ending without a sentence counts as garbage, a tag block before it is dropped
+                    if self.tag_block.take().is_some() {
+                        self.garbage += self.tag.len() + 2;
+                        self.tag.clear();
+                    }
+                    self.garbage += 1;
+                }
+            }
+            SentenceStatus::TAG => {
+                if byte == b'\\' {
+                    match TagBlock::parse(&self.tag) {
+                        Ok(tag_block) => {
+                            self.tag_block = Some(Box::new(tag_block));
+                            self.status = SentenceStatus::NONE;
+                        }
+                        Err(_) => self.discard(events, 1, DiscardReason::BadTagBlock),
+                    }
+                } else if byte == b'$' || byte == b'!' {
+                    self.discard(events, 0, DiscardReason::Interrupted);
+                    self.start(byte);
+                } else if byte == b'\r' || byte == b'\n' {
+                    self.discard(events, 1, DiscardReason::BadTerminator);
+                } else if self.tag.len() >= TAG_BLOCK_MAX_LENGTH {
+                    self.discard(events, 1, DiscardReason::Oversize);
+                } else {
+                    self.tag.push(byte);
+                }
+            }
+            SentenceStatus::STARTED => {
+                if byte == b'$' || byte == b'!' {
+                    self.discard(events, self.length, DiscardReason::Interrupted);
+                    self.start(byte);
+                } else if byte == b'\\' {
+                    self.discard(events, self.length, DiscardReason::Interrupted);
+                    self.start_tag();
+                } else if byte == b'\r' {
+                    self.status = SentenceStatus::TERMINATED;
+                } else if byte == b'\n' {
+                    if self.framing.accept_lf {
+                        self.framing_stats.lf_only += 1;
+                        self.complete(events, 1);
+                        self.after_lf = matches!(events.last(), Some(DecodeEvent::Sentence(_)));
+                    } else {
+                        self.discard(events, self.length + 1, DiscardReason::BadTerminator);
+                    }
+                } else if self.length >= self.max_length.saturating_sub(2) {
+                    self.length += 1;
+                    self.status = SentenceStatus::OVERSIZE;
+                } else {
+                    self.push(byte);
+                }
+            }
+            SentenceStatus::TERMINATED => {
+                if byte == b'\n' {
+                    self.complete(events, 2);
+                } else if self.framing.accept_cr {
+                    self.framing_stats.cr_only += 1;
+                    self.complete(events, 1);
+                    self.feed_byte(byte, events);
+                } else if byte == b'$' || byte == b'!' {
+                    self.discard(events, self.length + 1, DiscardReason::BadTerminator);
+                    self.start(byte);
+                } else if byte == b'\\' {
+                    self.discard(events, self.length + 1, DiscardReason::BadTerminator);
+                    self.start_tag();
+                } else {
+                    self.discard(events, self.length + 2, DiscardReason::BadTerminator);
+                }
+            }
+            SentenceStatus::OVERSIZE => {
+                if byte == b'$' || byte == b'!' {
+                    self.discard(events, self.length, DiscardReason::Oversize);
+                    self.start(byte);
+                } else if byte == b'\\' {
+                    self.discard(events, self.length, DiscardReason::Oversize);
+                    self.start_tag();
+                } else if byte == b'\n' {
+                    self.discard(events, self.length + 1, DiscardReason::Oversize);
+                } else {
+                    self.length += 1;
+                }
+            }
+        }
+    }
+
+    /// Emits the buffered sentence, `terminator` is the number of line ending bytes consumed.
+    fn complete(&mut self, events: &mut Vec<DecodeEvent>, terminator: usize) {
+        self.push(b'\r');
+        self.push(b'\n');
+        let sentence = NMEASentence {
+            characters: self.sentence.clone(),
+            tag_block: None,
+        };
+        let bytes = self.length - 2 + terminator;
+        match sentence.verify_checksum(self.checksum_policy) {
+            Ok(()) => {
+                if self.length > NMEA_SENTENCE_MAX_LENGTH {
+                    self.framing_stats.oversize += 1;
+                }
+                events.push(DecodeEvent::Sentence(NMEASentence {
+                    tag_block: self.tag_block.take(),
+                    ..sentence
+                }));
+                self.reset();
+            }
+            Err(ParseError::MissingChecksum) => {
+                self.discard(events, bytes, DiscardReason::MissingChecksum)
+            }
+            Err(_) => self.discard(events, bytes, DiscardReason::BadChecksum),
+        }
+    }
+
+    /// Starts a new sentence, a tag block received right before it is kept.
+    fn start(&mut self, byte: u8) {
+        self.sentence.clear();
+        self.length = 0;
+        self.push(byte);
+        self.status = SentenceStatus::STARTED;
+    }
+
+    fn start_tag(&mut self) {
+        self.reset();
+        self.status = SentenceStatus::TAG;
+    }
+
+    fn push(&mut self, byte: u8) {
+        self.sentence.push(byte);
+        self.length += 1;
+    }
+
+    fn reset(&mut self) {
+        self.sentence.clear();
+        self.length = 0;
+        self.tag.clear();
+        self.tag_block = None;
+        self.status = SentenceStatus::NONE;
+    }
+
+    /// Drops the current sentence together with its tag block, `bytes` excludes the tag block.
+    fn discard(&mut self, events: &mut Vec<DecodeEvent>, bytes: usize, reason: DiscardReason) {
+        let bytes = match (&self.status, &self.tag_block) {
+            (SentenceStatus::TAG, _) => bytes + self.tag.len() + 1,
+            (_, Some(_)) => bytes + self.tag.len() + 2,
+            _ => bytes,
+        };
+        self.reset();
+        self.discarded += bytes;
+        events.push(DecodeEvent::Discarded { bytes, reason });
+    }
+
+    fn flush_garbage(&mut self, events: &mut Vec<DecodeEvent>) {
+        if self.garbage > 0 {
+            let bytes = self.garbage;
+            self.garbage = 0;
+            self.discard(events, bytes, DiscardReason::NoStartDelimiter);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentences(events: &[DecodeEvent]) -> Vec<&[u8]> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                DecodeEvent::Sentence(sentence) => Some(sentence.as_bytes()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_feed_chunks() {
+        let mut decoder = NMEADecoder::new();
+
+        let events = decoder.feed(b"$SDDPT,87.4,0.0,*40\r\n$GPGSA,A,3,32,21");
+        assert_eq!(sentences(&events), vec![b"$SDDPT,87.4,0.0,*40\r\n"]);
+
+        let events = decoder.feed(b",,,,,,,,,,,1.50,0.90,1.20*0E\r");
+        assert!(events.is_empty());
+
+        let events = decoder.feed(b"\n!AIVDM,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,0*5C\r\n");
+        assert_eq!(
+            sentences(&events),
+            vec![
+                &b"$GPGSA,A,3,32,21,,,,,,,,,,,1.50,0.90,1.20*0E\r\n"[..],
+                &b"!AIVDM,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,0*5C\r\n"[..],
+            ]
+        );
+        assert_eq!(decoder.discarded(), 0);
+    }
+
+    #[test]
+    fn test_framing_policy() {
+        let data =
+            b"$SDDPT,87.4,0.0,*40\n$SDDPT,87.5,0.0,*41\r$SDDPT,87.6,0.0,*42\n\r$SDDPT,87.7,0.0,*43";
+
+        let mut decoder = NMEADecoder::new();
+        let mut events = decoder.feed(data);
+        events.extend(decoder.finish());
+        assert!(sentences(&events).is_empty());
+        assert_eq!(decoder.discarded(), data.len());
+
+        let mut decoder = NMEADecoder::new();
+        decoder.feed(b"$SDDPT,87.4,0.0,*40\r");
+        assert!(matches!(
+            decoder.finish()[..],
+            [DecodeEvent::Discarded {
+                bytes: 20,
+                reason: DiscardReason::BadTerminator
+            }]
+        ));
+
+        let mut decoder = NMEADecoder::new().with_framing(FramingPolicy::LENIENT);
+        let mut events = decoder.feed(data);
+        events.extend(decoder.finish());
+        assert_eq!(
+            sentences(&events),
+            vec![
+                b"$SDDPT,87.4,0.0,*40\r\n",
+                b"$SDDPT,87.5,0.0,*41\r\n",
+                b"$SDDPT,87.6,0.0,*42\r\n",
+                b"$SDDPT,87.7,0.0,*43\r\n",
+            ]
+        );
+        assert_eq!(
+            decoder.framing_stats(),
+            FramingStats {
+                lf_only: 2,
+                cr_only: 1,
+                unterminated_eof: 1,
+                oversize: 0,
+            }
+        );
+        assert_eq!(decoder.discarded(), 0);
+    }
+
+    #[test]
+    fn test_feed_tag_block() {
+        let mut decoder = NMEADecoder::new();
+
+        let events = decoder.feed(
+            b"\\s:r3669961,c:1503079517*70\\!AIVDM,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,0*5C\r\n",
+        );
+        let DecodeEvent::Sentence(sentence) = &events[0] else {
+            panic!("expected a sentence, got {:?}", events[0]);
+        };
+        assert_eq!(
+            sentence.tag_block().unwrap().source.as_deref(),
+            Some("r3669961")
+        );
+        assert_eq!(sentence.as_bytes()[0], b'!');
+
+        let mut written = vec![];
+        sentence.write_to(&mut written).unwrap();
+        assert_eq!(
+            written,
+            b"\\s:r3669961,c:1503079517*70\\!AIVDM,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,0*5C\r\n"
+        );
+
+        let events = decoder.feed(b"\\s:r3669961*00\\$SDDPT,87.4,0.0,*40\r\n");
+        assert!(matches!(
+            events[0],
+            DecodeEvent::Discarded {
+                bytes: 15,
+                reason: DiscardReason::BadTagBlock
+            }
+        ));
+        let DecodeEvent::Sentence(sentence) = &events[1] else {
+            panic!("expected a sentence, got {:?}", events[1]);
+        };
+        assert!(sentence.tag_block().is_none());
+    }
+
+    #[test]
+    fn test_line_ending_outside_sentence() {
+        let mut decoder = NMEADecoder::new();
+
+        let events = decoder.feed(b"\\s:x*31\\\r\n$SDDPT,87.4,0.0,*40\r\n");
+        assert!(matches!(
+            events[0],
+            DecodeEvent::Discarded {
+                bytes: 10,
+                reason: DiscardReason::NoStartDelimiter
+            }
+        ));
+        let DecodeEvent::Sentence(sentence) = &events[1] else {
+            panic!("expected a sentence, got {:?}", events[1]);
+        };
+        assert!(sentence.tag_block().is_none());
+
+        let events = decoder.feed(b"\r\n$SDDPT,87.4,0.0,*40\r\n\n");
+        assert!(matches!(
+            events[..],
+            [
+                DecodeEvent::Discarded { bytes: 2, .. },
+                DecodeEvent::Sentence(_),
+                DecodeEvent::Discarded { bytes: 1, .. }
+            ]
+        ));
+        assert_eq!(decoder.discarded(), 13);
+    }
+
+    #[test]
+    fn test_checksum_policy() {
+        let data = b"$SDDPT,87.4,0.0,*40\r\n$SDDPT,87.5,0.0,*40\r\n$SDDPT,87.6,0.0,\r\n";
+
+        let events = NMEADecoder::new().feed(data);
+        assert_eq!(
+            sentences(&events),
+            vec![
+                &b"$SDDPT,87.4,0.0,*40\r\n"[..],
+                &b"$SDDPT,87.6,0.0,\r\n"[..]
+            ]
+        );
+        assert!(matches!(
+            events[1],
+            DecodeEvent::Discarded {
+                bytes: 21,
+                reason: DiscardReason::BadChecksum
+            }
+        ));
+
+        let events = NMEADecoder::new()
+            .with_checksum_policy(ChecksumPolicy::Required)
+            .feed(data);
+        assert_eq!(sentences(&events), vec![b"$SDDPT,87.4,0.0,*40\r\n"]);
+
+        let events = NMEADecoder::new()
+            .with_checksum_policy(ChecksumPolicy::Ignore)
+            .feed(data);
+        assert_eq!(sentences(&events).len(), 3);
+    }
+
+    #[test]
+    fn test_feed_resynchronise() {
+        let mut decoder = NMEADecoder::new();
+
+        let events = decoder.feed(b"noise$SDDPT,87.4$SDDPT,87.4,0.0,*40\r\n$SDDPT\rX");
+        assert!(matches!(
+            events[0],
+            DecodeEvent::Discarded {
+                bytes: 5,
+                reason: DiscardReason::NoStartDelimiter
+            }
+        ));
+        assert!(matches!(
+            events[1],
+            DecodeEvent::Discarded {
+                bytes: 11,
+                reason: DiscardReason::Interrupted
+            }
+        ));
+        assert_eq!(sentences(&events), vec![b"$SDDPT,87.4,0.0,*40\r\n"]);
+        assert!(matches!(
+            events[3],
+            DecodeEvent::Discarded {
+                bytes: 8,
+                reason: DiscardReason::BadTerminator
+            }
+        ));
+        assert_eq!(decoder.discarded(), 24);
+    }
+
+    #[test]
+    fn test_max_length() {
+        let pubx = b"$PUBX,00,081350.00,4717.113210,N,00833.915187,E,546.589,G3,2.1,2.0,0.007,77.52,0.007,,0.92,1.19,0.77,9,0,0*5F\r\n";
+        let data = [&pubx[..], b"$SDDPT,87.4,0.0,*40\r\n"].concat();
+
+        let events = NMEADecoder::new().feed(&data);
+        assert!(matches!(
+            events[0],
+            DecodeEvent::Discarded {
+                bytes,
+                reason: DiscardReason::Oversize
+            } if bytes == pubx.len()
+        ));
+        assert_eq!(sentences(&events), vec![b"$SDDPT,87.4,0.0,*40\r\n"]);
+
+        let mut decoder = NMEADecoder::new().with_max_length(128);
+        let events = decoder.feed(&data);
+        assert_eq!(
+            sentences(&events),
+            vec![&pubx[..], b"$SDDPT,87.4,0.0,*40\r\n"]
+        );
+        assert_eq!(decoder.framing_stats().oversize, 1);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::error::ParseError;
+use crate::primitives::coordinates::Coordinate;
+use crate::primitives::escape::encode_escaped;
+use crate::sentence::{calculate_checksum, NMEASentence, NMEASentenceFormatter};
+use crate::talker::Talker;
+use alloc::format;
+use alloc::vec::Vec;
+use chrono::{Datelike, NaiveDate, NaiveTime, Timelike};
+
+/// Inverse of [FromField](crate::FromField), the characters a value is transmitted as.
+pub trait ToField {
+    fn to_field(&self) -> &'static str;
+}
+
+/// Typed sentences that can be written back as an approved parametric sentence.
+pub trait Encode {
+    fn formatter(&self) -> NMEASentenceFormatter;
+
+    /// Writes the data fields in order, see [FieldWriter]
+    fn encode_fields(&self, writer: &mut FieldWriter);
+
+    /// Builds `$<talker><formatter>,<fields>*hh\r\n`, failing with [ParseError::TooLong]
+    /// if the result exceeds [NMEA_SENTENCE_MAX_LENGTH](crate::NMEA_SENTENCE_MAX_LENGTH).
+    fn to_sentence(&self, talker: Talker) -> Result<NMEASentence, ParseError> {
+        let mut writer = FieldWriter::new();
+        self.encode_fields(&mut writer);
+        encode_sentence(talker, self.formatter(), &writer.content)
+    }
+}
+
+pub(crate) fn encode_sentence(
+    talker: Talker,
+    formatter: NMEASentenceFormatter,
+    content: &[u8],
+) -> Result<NMEASentence, ParseError> {
+    let mut characters = Vec::with_capacity(content.len() + 11);
+    characters.push(b'$');
+    characters.extend_from_slice(&talker.code());
+    characters.extend(formatter.iter().map(|c| *c as u8));
+    characters.extend_from_slice(content);
+    let checksum = calculate_checksum(&characters);
+    characters.extend_from_slice(format!("*{:02X}\r\n", checksum).as_bytes());
+    NMEASentence::from_bytes(&characters).ok_or(ParseError::TooLong(characters.len()))
+}
+
+/// Collects the comma separated data fields of a sentence, every method appends one field
+/// (two for coordinates) and writes a null field for `None`.
+#[derive(Debug, Default)]
+pub struct FieldWriter {
+    content: Vec<u8>,
+}
+
+impl FieldWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The fields written so far, each preceded by its `,`
+    pub fn content(&self) -> &[u8] {
+        &self.content
+    }
+
+    /// Appends `field`, reserved characters are escaped as `^hh`
+    pub fn raw(&mut self, field: &[u8]) -> &mut Self {
+        self.push_field(&encode_escaped(field))
+    }
+
+    /// Appends a field built by this writer, it never contains reserved characters
+    fn push_field(&mut self, field: &[u8]) -> &mut Self {
+        self.content.push(b',');
+        self.content.extend_from_slice(field);
+        self
+    }
+
+    pub fn null(&mut self) -> &mut Self {
+        self.push_field(b"")
+    }
+
+    /// Text field, reserved characters are escaped as `^hh`
+    pub fn str(&mut self, value: Option<&str>) -> &mut Self {
+        match value {
+            Some(value) => self.raw(value.as_bytes()),
+            None => self.null(),
+        }
+    }
+
+    /// Decimal number with a fixed number of `decimals`
+    pub fn float(&mut self, value: Option<impl Into<f64>>, decimals: usize) -> &mut Self {
+        match value {
+            Some(value) => self.push_field(format!("{:.*}", decimals, value.into()).as_bytes()),
+            None => self.null(),
+        }
+    }
+
+    /// Unsigned integer padded with leading zeros to `width` digits
+    pub fn uint(&mut self, value: Option<impl Into<u32>>, width: usize) -> &mut Self {
+        match value {
+            Some(value) => {
+                self.push_field(format!("{:0width$}", value.into(), width = width).as_bytes())
+            }
+            None => self.null(),
+        }
+    }
+
+    /// Upper case hex digits padded to `width`
+    pub fn hex(&mut self, value: Option<u32>, width: usize) -> &mut Self {
+        match value {
+            Some(value) => self.push_field(format!("{:0width$X}", value, width = width).as_bytes()),
+            None => self.null(),
+        }
+    }
+
+    /// Single character field, a reserved character is escaped as `^hh`
+    pub fn char(&mut self, value: Option<char>) -> &mut Self {
+        match value {
+            Some(value) => self.raw(value.encode_utf8(&mut [0; 4]).as_bytes()),
+            None => self.null(),
+        }
+    }
+
+    /// UTC time as `hhmmss.ss`
+    pub fn time(&mut self, value: Option<NaiveTime>) -> &mut Self {
+        match value {
+            Some(time) => self.push_field(
+                format!(
+                    "{:02}{:02}{:02}.{:02}",
+                    time.hour(),
+                    time.minute(),
+                    time.second(),
+                    // Leap seconds are kept in the nanoseconds, the field cannot express them
+                    (time.nanosecond() % 1_000_000_000) / 10_000_000
+                )
+                .as_bytes(),
+            ),
+            None => self.null(),
+        }
+    }
+
+    /// Date as `ddmmyy`
+    pub fn date(&mut self, value: Option<NaiveDate>) -> &mut Self {
+        match value {
+            Some(date) => self.push_field(
+                format!(
+                    "{:02}{:02}{:02}",
+                    date.day(),
+                    date.month(),
+                    date.year().rem_euclid(100)
+                )
+                .as_bytes(),
+            ),
+            None => self.null(),
+        }
+    }
+
+    /// Latitude as `ddmm.mmmm` followed by its `N`/`S` field
+    pub fn latitude(&mut self, value: Option<Coordinate>) -> &mut Self {
+        self.coordinate(value, 2)
+    }
+
+    /// Longitude as `dddmm.mmmm` followed by its `E`/`W` field
+    pub fn longitude(&mut self, value: Option<Coordinate>) -> &mut Self {
+        self.coordinate(value, 3)
+    }
+
+    fn coordinate(&mut self, value: Option<Coordinate>, width: usize) -> &mut Self {
+        let Some(coordinate) = value else {
+            return self.null().null();
+        };
+        // Round to ten thousandths first so 59.99999 minutes carry over into the degrees
+        let mut degrees = coordinate.degrees();
+        let mut minutes = (coordinate.minutes() as f64 * 10_000.0 + 0.5) as u32;
+        if minutes >= 600_000 {
+            degrees += 1;
+            minutes -= 600_000;
+        }
+        self.push_field(
+            format!(
+                "{:0width$}{:02}.{:04}",
+                degrees,
+                minutes / 10_000,
+                minutes % 10_000,
+                width = width
+            )
+            .as_bytes(),
+        )
+        .char(Some(coordinate.direction()))
+    }
+
+    pub fn enumeration<T: ToField>(&mut self, value: Option<&T>) -> &mut Self {
+        match value {
+            Some(value) => self.raw(value.to_field().as_bytes()),
+            None => self.null(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Text<'a>(&'a str);
+
+    impl Encode for Text<'_> {
+        fn formatter(&self) -> NMEASentenceFormatter {
+            ['T', 'X', 'T']
+        }
+
+        fn encode_fields(&self, writer: &mut FieldWriter) {
+            writer
+                .uint(Some(1u8), 2)
+                .uint(Some(1u8), 2)
+                .uint(Some(25u8), 2)
+                .str(Some(self.0));
+        }
+    }
+
+    #[test]
+    fn test_encode_fields() {
+        let mut writer = FieldWriter::new();
+        writer
+            .time(NaiveTime::from_hms_milli_opt(9, 5, 7, 250))
+            .latitude(Some(Coordinate::new(48, 7.03799, 'N')))
+            .longitude(Some(Coordinate::new(11, 59.999999, 'E')))
+            .float(None::<f32>, 1)
+            .date(NaiveDate::from_ymd_opt(2024, 3, 1))
+            .hex(Some(0x2A), 2);
+        assert_eq!(
+            writer.content,
+            b",090507.25,4807.0380,N,01200.0000,E,,010324,2A"
+        );
+
+        let sentence = Text("DEPTH, SHALLOW").to_sentence(Talker::Sounder).unwrap();
+        assert_eq!(
+            sentence.as_bytes(),
+            b"$SDTXT,01,01,25,DEPTH^2C SHALLOW*48\r\n"
+        );
+        assert!(sentence.valid());
+
+        let mut writer = FieldWriter::new();
+        writer.char(Some(',')).raw(b"1*2");
+        assert_eq!(writer.content(), b",^2C,1^2A2");
+
+        assert_eq!(
+            Text(&"A".repeat(70))
+                .to_sentence(Talker::Sounder)
+                .unwrap_err(),
+            ParseError::TooLong(91)
+        );
+    }
+}
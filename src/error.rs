@@ -0,0 +1,80 @@
+use crate::sentence::{NMEASentenceFormatter, SentenceType};
+use core::error::Error;
+use core::fmt;
+
+/// Everything that can go wrong while turning raw bytes into a typed sentence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The sentence does not start with `$` or `!`
+    BadStartDelimiter(u8),
+    /// The sentence is shorter than the smallest possible address field
+    TooShort(usize),
+    /// The transmitted checksum does not match the calculated one
+    BadChecksum { expected: u8, actual: u8 },
+    /// The sentence has no checksum although the [ChecksumPolicy](crate::ChecksumPolicy) requires one
+    MissingChecksum,
+    /// The characters after `*` are not two hex digits
+    MalformedChecksum,
+    /// The sentence has more fields than a [FieldsRef](crate::FieldsRef) can hold
+    TooManyFields(usize),
+    /// The sentence ended before the field with this index
+    MissingField(usize),
+    /// The field with this index could not be parsed, `offset` is the byte offset of the field within the content
+    UnparsableField { index: usize, offset: usize },
+    /// A `^` inside a field is not followed by two hex digits, `offset` is relative to the field
+    BadEscape { offset: usize },
+    /// An encoded sentence would be longer than [NMEA_SENTENCE_MAX_LENGTH](crate::NMEA_SENTENCE_MAX_LENGTH)
+    TooLong(usize),
+    /// The sentence is not of the type the caller asked for, e.g. not a query
+    UnexpectedSentenceType(SentenceType),
+    /// No formatter is known for this mnemonic
+    UnknownFormatter(NMEASentenceFormatter),
+    /// A tag block is not of the form `\c:v,c:v*hh\`
+    BadTagBlock,
+    /// An IEC 61162-450 datagram starts with neither `UdPbC\0` nor `RaUdP\0`
+    BadDatagramHeader,
+}
+
+impl ParseError {
+    pub(crate) fn unparsable_field(fields: &[&[u8]], index: usize) -> Self {
+        let offset = fields.iter().take(index).map(|field| field.len() + 1).sum();
+        ParseError::UnparsableField { index, offset }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::BadStartDelimiter(byte) => {
+                write!(f, "Invalid start delimiter: {:?}", *byte as char)
+            }
+            ParseError::TooShort(length) => write!(f, "Sentence too short: {} bytes", length),
+            ParseError::BadChecksum { expected, actual } => write!(
+                f,
+                "Checksum mismatch: expected {:02X}, got {:02X}",
+                expected, actual
+            ),
+            ParseError::MissingChecksum => write!(f, "Missing checksum"),
+            ParseError::MalformedChecksum => write!(f, "Malformed checksum"),
+            ParseError::TooManyFields(limit) => write!(f, "More than {} fields", limit),
+            ParseError::MissingField(index) => write!(f, "Missing field {}", index),
+            ParseError::UnparsableField { index, offset } => {
+                write!(f, "Unparsable field {} at byte offset {}", index, offset)
+            }
+            ParseError::BadEscape { offset } => write!(f, "Invalid escape at byte {}", offset),
+            ParseError::TooLong(length) => write!(f, "Sentence too long: {} bytes", length),
+            ParseError::UnexpectedSentenceType(sentence_type) => {
+                write!(f, "Unexpected sentence type: {:?}", sentence_type)
+            }
+            ParseError::UnknownFormatter(formatter) => write!(
+                f,
+                "Unknown formatter: {}{}{}",
+                formatter[0], formatter[1], formatter[2]
+            ),
+            ParseError::BadTagBlock => write!(f, "Malformed tag block"),
+            ParseError::BadDatagramHeader => write!(f, "Unknown datagram header"),
+        }
+    }
+}
+
+impl Error for ParseError {}
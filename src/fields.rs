@@ -0,0 +1,191 @@
+use crate::error::ParseError;
+use crate::primitives::coordinates::{Coordinate, CoordinateError};
+use chrono::{NaiveDate, NaiveTime};
+use core::str::FromStr;
+
+/// Values of a single character or mnemonic field, like a mode indicator.
+pub trait FromField: Sized {
+    /// `None` if the field holds a value that is not part of the enumeration
+    fn from_field(field: &[u8]) -> Option<Self>;
+}
+
+/// Typed access to the comma separated fields of a sentence.\
+/// Every getter returns `Ok(None)` for a null field, fields beyond the end of the
+/// sentence count as null so optional trailing fields of newer NMEA versions need
+/// no special casing. Malformed fields fail with [ParseError::UnparsableField].
+pub trait Fields {
+    /// The raw field, `None` if it is null or missing
+    fn field(&self, index: usize) -> Option<&[u8]>;
+
+    /// Error for the field with `index`, carrying its position within the content
+    fn unparsable(&self, index: usize) -> ParseError;
+
+    fn str(&self, index: usize) -> Result<Option<&str>, ParseError> {
+        self.field(index)
+            .map(|field| core::str::from_utf8(field).map_err(|_| self.unparsable(index)))
+            .transpose()
+    }
+
+    /// Parses the field with [FromStr]
+    fn parse<T: FromStr>(&self, index: usize) -> Result<Option<T>, ParseError> {
+        self.str(index)?
+            .map(|field| T::from_str(field).map_err(|_| self.unparsable(index)))
+            .transpose()
+    }
+
+    fn f32(&self, index: usize) -> Result<Option<f32>, ParseError> {
+        self.parse(index)
+    }
+
+    fn f64(&self, index: usize) -> Result<Option<f64>, ParseError> {
+        self.parse(index)
+    }
+
+    fn u8(&self, index: usize) -> Result<Option<u8>, ParseError> {
+        self.parse(index)
+    }
+
+    fn u16(&self, index: usize) -> Result<Option<u16>, ParseError> {
+        self.parse(index)
+    }
+
+    fn u32(&self, index: usize) -> Result<Option<u32>, ParseError> {
+        self.parse(index)
+    }
+
+    fn i32(&self, index: usize) -> Result<Option<i32>, ParseError> {
+        self.parse(index)
+    }
+
+    /// A field of exactly one character, like a status flag
+    fn char(&self, index: usize) -> Result<Option<char>, ParseError> {
+        match self.field(index) {
+            None => Ok(None),
+            Some([byte]) if byte.is_ascii() => Ok(Some(*byte as char)),
+            Some(_) => Err(self.unparsable(index)),
+        }
+    }
+
+    /// A field of hex digits, like a bit mask or a message id. Signs are not allowed.
+    fn hex(&self, index: usize) -> Result<Option<u32>, ParseError> {
+        self.str(index)?
+            .map(|field| {
+                if !field.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+                    return Err(self.unparsable(index));
+                }
+                u32::from_str_radix(field, 16).map_err(|_| self.unparsable(index))
+            })
+            .transpose()
+    }
+
+    /// UTC time as `hhmmss.ss`, the fractional seconds are optional
+    fn time(&self, index: usize) -> Result<Option<NaiveTime>, ParseError> {
+        self.str(index)?
+            .map(|field| {
+                NaiveTime::parse_from_str(field, "%H%M%S%.f")
+                    .or_else(|_| NaiveTime::parse_from_str(field, "%H%M%S"))
+                    .map_err(|_| self.unparsable(index))
+            })
+            .transpose()
+    }
+
+    /// Date as `ddmmyy`
+    fn date(&self, index: usize) -> Result<Option<NaiveDate>, ParseError> {
+        self.str(index)?
+            .map(|field| {
+                NaiveDate::parse_from_str(field, "%d%m%y").map_err(|_| self.unparsable(index))
+            })
+            .transpose()
+    }
+
+    /// Latitude `llll.ll` at `index` followed by its `N`/`S` field
+    fn latitude(&self, index: usize) -> Result<Option<Coordinate>, ParseError> {
+        coordinate(self, index, Coordinate::from_latitude_string)
+    }
+
+    /// Longitude `yyyyy.yy` at `index` followed by its `E`/`W` field
+    fn longitude(&self, index: usize) -> Result<Option<Coordinate>, ParseError> {
+        coordinate(self, index, Coordinate::from_longitude_string)
+    }
+
+    /// A field holding one value of the enumeration `T`
+    fn enumeration<T: FromField>(&self, index: usize) -> Result<Option<T>, ParseError> {
+        self.field(index)
+            .map(|field| T::from_field(field).ok_or_else(|| self.unparsable(index)))
+            .transpose()
+    }
+}
+
+fn coordinate<F: Fields + ?Sized>(
+    fields: &F,
+    index: usize,
+    from_string: fn(&str, char) -> Result<Coordinate, CoordinateError>,
+) -> Result<Option<Coordinate>, ParseError> {
+    let Some(value) = fields.str(index)? else {
+        return Ok(None);
+    };
+    let direction = fields
+        .char(index + 1)?
+        .ok_or_else(|| fields.unparsable(index + 1))?;
+    if !value.is_ascii() {
+        return Err(fields.unparsable(index));
+    }
+    from_string(value, direction)
+        .map(Some)
+        .map_err(|_| fields.unparsable(index))
+}
+
+impl Fields for [&[u8]] {
+    fn field(&self, index: usize) -> Option<&[u8]> {
+        self.get(index).copied().filter(|field| !field.is_empty())
+    }
+
+    fn unparsable(&self, index: usize) -> ParseError {
+        ParseError::unparsable_field(self, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_fields() {
+        let fields: &[&[u8]] = &[
+            b"123519.00",
+            b"4807.038",
+            b"N",
+            b"01131.000",
+            b"E",
+            b"230394",
+            b"",
+            b"1A",
+            b"x.5",
+            b"+A",
+        ];
+
+        assert_eq!(fields.time(0).unwrap(), NaiveTime::from_hms_opt(12, 35, 19));
+        let latitude = fields.latitude(1).unwrap().unwrap();
+        assert_eq!((latitude.degrees(), latitude.direction()), (48, 'N'));
+        let longitude = fields.longitude(3).unwrap().unwrap();
+        assert_eq!((longitude.degrees(), longitude.direction()), (11, 'E'));
+        assert_eq!(
+            fields.date(5).unwrap(),
+            NaiveDate::from_ymd_opt(1994, 3, 23)
+        );
+        assert_eq!(fields.f32(6).unwrap(), None);
+        assert_eq!(fields.hex(7).unwrap(), Some(0x1A));
+        assert_eq!(fields.u8(20).unwrap(), None);
+
+        assert_eq!(
+            fields.f32(8).unwrap_err(),
+            ParseError::UnparsableField {
+                index: 8,
+                offset: 44
+            }
+        );
+        assert_eq!(fields.hex(9).unwrap_err(), fields.unparsable(9));
+        assert!(fields.char(1).is_err());
+        assert!(fields.latitude(3).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::error::ParseError;
+use crate::sentence::{ChecksumPolicy, NMEA_SENTENCE_MAX_LENGTH};
+use crate::sentence_ref::SentenceRef;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FramerStatus {
+    Idle,
+    Filling,
+    Overflow,
+}
+
+/// Push based sentence framer for targets without an allocator.\
+/// Bytes are stored in an inline buffer of `N` bytes, a sentence is handed out as a
+/// [SentenceRef] into that buffer as soon as its line ends with `\n`. A tag block in
+/// front of a sentence is kept, `N` has to leave room for it.
+#[derive(Debug, Clone)]
+pub struct SentenceFramer<const N: usize = NMEA_SENTENCE_MAX_LENGTH> {
+    buffer: [u8; N],
+    length: usize,
+    status: FramerStatus,
+    checksum_policy: ChecksumPolicy,
+    discarded: usize,
+}
+
+impl<const N: usize> Default for SentenceFramer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> SentenceFramer<N> {
+    pub fn new() -> Self {
+        SentenceFramer {
+            buffer: [0; N],
+            length: 0,
+            status: FramerStatus::Idle,
+            checksum_policy: ChecksumPolicy::default(),
+            discarded: 0,
+        }
+    }
+
+    pub fn with_checksum_policy(mut self, checksum_policy: ChecksumPolicy) -> Self {
+        self.checksum_policy = checksum_policy;
+        self
+    }
+
+    /// Bytes outside of sentences and of sentences interrupted by a new start delimiter
+    pub fn discarded(&self) -> usize {
+        self.discarded
+    }
+
+    /// Adds one byte, returning the sentence it terminates.
+    /// Lines longer than `N` fail with [ParseError::TooLong], sentences violating the
+    /// [ChecksumPolicy] with the corresponding checksum error.
+    pub fn push(&mut self, byte: u8) -> Option<Result<SentenceRef<'_>, ParseError>> {
+        match (self.status, byte) {
+            (_, b'\n') => return self.complete(),
+            (_, b'\r') => {}
+            (FramerStatus::Idle, b'$' | b'!' | b'\\') => self.start(byte),
+            (FramerStatus::Idle, _) => self.discarded += 1,
+            (FramerStatus::Filling, b'$' | b'!') if !self.after_tag_block() => {
+                self.discarded += self.length;
+                self.start(byte);
+            }
+            (FramerStatus::Filling, _) if self.length == N => {
+                self.length += 1;
+                self.status = FramerStatus::Overflow;
+            }
+            (FramerStatus::Filling, _) => {
+                self.buffer[self.length] = byte;
+                self.length += 1;
+            }
+            (FramerStatus::Overflow, _) => self.length += 1,
+        }
+        None
+    }
+
+    fn start(&mut self, byte: u8) {
+        self.buffer[0] = byte;
+        self.length = 1;
+        self.status = FramerStatus::Filling;
+    }
+
+    /// The buffer holds exactly a tag block, the sentence belonging to it starts next
+    fn after_tag_block(&self) -> bool {
+        let buffer = &self.buffer[..self.length];
+        buffer.len() > 1 && buffer[0] == b'\\' && buffer[buffer.len() - 1] == b'\\'
+    }
+
+    fn complete(&mut self) -> Option<Result<SentenceRef<'_>, ParseError>> {
+        let status = self.status;
+        self.status = FramerStatus::Idle;
+        match status {
+            FramerStatus::Idle => None,
+            FramerStatus::Overflow => Some(Err(ParseError::TooLong(self.length))),
+            FramerStatus::Filling => Some(
+                SentenceRef::parse(&self.buffer[..self.length]).and_then(|sentence| {
+                    self.checksum_policy.check(sentence.checksum_status())?;
+                    Ok(sentence)
+                }),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_without_alloc() {
+        let mut framer = SentenceFramer::<128>::new();
+        let data = b"noise\\s:GP0001*5F\\$GPDPT,87.4,0.0,*40\r\n$GPDPT,87.5$SDDPT,87.4,0.0,*41\r\n";
+
+        let mut sentences = 0;
+        let mut errors = 0;
+        for byte in data {
+            match framer.push(*byte) {
+                Some(Ok(sentence)) => {
+                    assert_eq!(sentence.content(), b"87.4,0.0,");
+                    sentences += 1;
+                }
+                Some(Err(err)) => {
+                    assert_eq!(
+                        err,
+                        ParseError::BadChecksum {
+                            expected: 0x40,
+                            actual: 0x41
+                        }
+                    );
+                    errors += 1;
+                }
+                None => {}
+            }
+        }
+        assert_eq!((sentences, errors), (1, 1));
+        assert_eq!(framer.discarded(), 5 + 11);
+
+        let mut framer = SentenceFramer::<16>::new();
+        let result = b"$GPDPT,87.4,0.0,*40\r\n"
+            .iter()
+            .filter_map(|byte| framer.push(*byte).map(|result| result.err()))
+            .next();
+        assert_eq!(result, Some(Some(ParseError::TooLong(19))));
+    }
+}
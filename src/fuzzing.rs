@@ -0,0 +1,49 @@
+use crate::approved_sentence_formatters::FormatterRegistry;
+use crate::proprietary_sentence_formatters::ProprietaryRegistry;
+use crate::{
+    Address, ChecksumPolicy, Fields, FramingPolicy, NMEASentenceReader, Query, SentenceFramer,
+    SentenceRef,
+};
+
+/// Runs `data` through every stage of the decode path, only the absence of panics matters.\
+/// Shared by the fuzz target and the property tests of the reader.
+pub fn decode_everything(data: &[u8]) {
+    let formatters = FormatterRegistry::new();
+    let proprietary = ProprietaryRegistry::new();
+    let reader = NMEASentenceReader::new(data)
+        .with_framing(FramingPolicy::LENIENT)
+        .with_checksum_policy(ChecksumPolicy::Ignore)
+        .with_max_length(256);
+    for sentence in reader {
+        let Ok(mut content) = sentence.decode() else {
+            continue;
+        };
+        if let Address::Approved(address) = content.address.address {
+            let _ = formatters
+                .select_sentence_formatter(&address.formatter, &content.parse_content_fields());
+        }
+        let Ok(fields) = content.fields() else {
+            continue;
+        };
+        for index in 0..fields.len() {
+            let _ = fields.time(index);
+            let _ = fields.date(index);
+            let _ = fields.latitude(index);
+            let _ = fields.longitude(index);
+            let _ = fields.hex(index);
+            let _ = fields.char(index);
+            let _ = content.text_field(index);
+        }
+    }
+    for sentence in SentenceRef::parse_all(data).flatten() {
+        let _ = sentence.tag_block();
+        let _ = sentence.fields();
+        let _ = sentence.to_sentence();
+        let _ = Query::from_sentence(&sentence);
+        let _ = proprietary.parse_sentence(&sentence);
+    }
+    let mut framer = SentenceFramer::<128>::new().with_checksum_policy(ChecksumPolicy::Ignore);
+    for byte in data {
+        let _ = framer.push(*byte);
+    }
+}
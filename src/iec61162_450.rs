@@ -0,0 +1,284 @@
+use crate::decoder::{DecodeEvent, FramingPolicy, NMEADecoder};
+use crate::error::ParseError;
+use crate::sentence::NMEASentence;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket};
+
+/// Header of a datagram carrying tag blocked sentences
+pub const SENTENCE_HEADER: &[u8; 6] = b"UdPbC\0";
+/// Header of a binary image transfer datagram
+pub const BINARY_IMAGE_HEADER: &[u8; 6] = b"RaUdP\0";
+
+const MAX_DATAGRAM_LENGTH: usize = 65507;
+
+/// Transmission groups of IEC 61162-450, each one is a fixed multicast address and port
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MulticastGroup {
+    MISC,
+    TGTD,
+    SATD,
+    NAVD,
+    VDRD,
+    RCOM,
+    TIME,
+    PROP,
+    USR1,
+    USR2,
+    USR3,
+    USR4,
+    USR5,
+    USR6,
+    USR7,
+    USR8,
+    BAM1,
+    BAM2,
+}
+
+impl MulticastGroup {
+    pub const ALL: [MulticastGroup; 18] = [
+        MulticastGroup::MISC,
+        MulticastGroup::TGTD,
+        MulticastGroup::SATD,
+        MulticastGroup::NAVD,
+        MulticastGroup::VDRD,
+        MulticastGroup::RCOM,
+        MulticastGroup::TIME,
+        MulticastGroup::PROP,
+        MulticastGroup::USR1,
+        MulticastGroup::USR2,
+        MulticastGroup::USR3,
+        MulticastGroup::USR4,
+        MulticastGroup::USR5,
+        MulticastGroup::USR6,
+        MulticastGroup::USR7,
+        MulticastGroup::USR8,
+        MulticastGroup::BAM1,
+        MulticastGroup::BAM2,
+    ];
+
+    /// Groups are numbered from 1, group n uses 239.192.0.n on port 60000 + n
+    fn number(&self) -> u8 {
+        *self as u8 + 1
+    }
+
+    pub fn address(&self) -> SocketAddrV4 {
+        SocketAddrV4::new(
+            Ipv4Addr::new(239, 192, 0, self.number()),
+            60000 + self.number() as u16,
+        )
+    }
+
+    pub fn from_address(address: SocketAddrV4) -> Option<Self> {
+        MulticastGroup::ALL
+            .into_iter()
+            .find(|group| group.address() == address)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Datagram {
+    Sentences(Vec<NMEASentence>),
+    /// Payload following the binary image header, left to the application
+    BinaryImage(Vec<u8>),
+}
+
+/// Splits a received datagram by its header, the last sentence may lack its terminator.
+pub fn parse_datagram(data: &[u8]) -> Result<Datagram, ParseError> {
+    if let Some(payload) = data.strip_prefix(SENTENCE_HEADER) {
+        // The end of the datagram ends the last sentence
+        let mut decoder = NMEADecoder::new().with_framing(FramingPolicy {
+            accept_unterminated_eof: true,
+            ..FramingPolicy::STRICT
+        });
+        let mut events = decoder.feed(payload);
+        events.extend(decoder.finish());
+        let sentences = events
+            .into_iter()
+            .filter_map(|event| match event {
+                DecodeEvent::Sentence(sentence) => Some(sentence),
+                DecodeEvent::Discarded { .. } => None,
+            })
+            .collect();
+        Ok(Datagram::Sentences(sentences))
+    } else if let Some(payload) = data.strip_prefix(BINARY_IMAGE_HEADER) {
+        Ok(Datagram::BinaryImage(payload.to_vec()))
+    } else {
+        Err(ParseError::BadDatagramHeader)
+    }
+}
+
+/// Builds a sentence datagram, every sentence is written with its tag block.
+pub fn encode_datagram(sentences: &[NMEASentence]) -> io::Result<Vec<u8>> {
+    let mut datagram = SENTENCE_HEADER.to_vec();
+    for sentence in sentences {
+        sentence.write_to(&mut datagram)?;
+    }
+    Ok(datagram)
+}
+
+/// Joins one transmission group and yields its datagrams.
+pub struct Receiver {
+    socket: UdpSocket,
+    group: MulticastGroup,
+    destination: Option<String>,
+}
+
+impl Receiver {
+    /// Joins `group` on the network interface with the address `interface`.
+    pub fn join(group: MulticastGroup, interface: Ipv4Addr) -> io::Result<Self> {
+        let address = group.address();
+        let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+        socket.set_reuse_address(true)?;
+        socket.bind(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, address.port())).into())?;
+        socket.join_multicast_v4(address.ip(), &interface)?;
+        Ok(Receiver {
+            socket: socket.into(),
+            group,
+            destination: None,
+        })
+    }
+
+    /// Only keep sentences addressed to `destination` (tag block `d:`) or to nobody in particular.
+    pub fn with_destination(mut self, destination: &str) -> Self {
+        self.destination = Some(destination.to_string());
+        self
+    }
+
+    pub fn group(&self) -> MulticastGroup {
+        self.group
+    }
+
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Blocks until the next datagram arrives.
+    /// A datagram with an unknown header fails with [io::ErrorKind::InvalidData]
+    /// wrapping [ParseError::BadDatagramHeader], the receiver stays usable.
+    pub fn recv(&self) -> io::Result<Datagram> {
+        let mut buf = vec![0; MAX_DATAGRAM_LENGTH];
+        let (length, _) = self.socket.recv_from(&mut buf)?;
+        match parse_datagram(&buf[..length]) {
+            Ok(Datagram::Sentences(mut sentences)) => {
+                if let Some(destination) = &self.destination {
+                    sentences.retain(|sentence| {
+                        match sentence
+                            .tag_block()
+                            .and_then(|tag| tag.destination.as_ref())
+                        {
+                            Some(target) => target == destination,
+                            None => true,
+                        }
+                    });
+                }
+                Ok(Datagram::Sentences(sentences))
+            }
+            Ok(datagram) => Ok(datagram),
+            Err(err) => Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+        }
+    }
+}
+
+/// Sends sentences to one transmission group, tagging them with the source and a line count.
+pub struct Sender {
+    socket: UdpSocket,
+    group: MulticastGroup,
+    source: String,
+    line_count: u32,
+}
+
+impl Sender {
+    /// `source` is the system function ID of this device, e.g. `GP0001`.
+    pub fn new(source: &str, group: MulticastGroup, interface: Ipv4Addr) -> io::Result<Self> {
+        let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+        socket.set_multicast_if_v4(&interface)?;
+        socket.set_multicast_loop_v4(true)?;
+        socket.bind(&SocketAddr::from((interface, 0)).into())?;
+        Ok(Sender {
+            socket: socket.into(),
+            group,
+            source: source.to_string(),
+            line_count: 0,
+        })
+    }
+
+    pub fn group(&self) -> MulticastGroup {
+        self.group
+    }
+
+    pub fn send(&mut self, sentence: &NMEASentence) -> io::Result<()> {
+        self.line_count = self.line_count % 999 + 1;
+        let mut tag_block = sentence.tag_block().cloned().unwrap_or_default();
+        tag_block.source = Some(self.source.clone());
+        tag_block.line_count = Some(self.line_count);
+        let sentence = sentence.clone().with_tag_block(tag_block);
+        let datagram = encode_datagram(&[sentence])?;
+        self.socket.send_to(&datagram, self.group.address())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_datagram() {
+        let datagram = parse_datagram(
+            b"UdPbC\0\\s:GP0001,n:1*16\\$GPDPT,87.4,0.0,*40\r\n$GPDPT,87.5,0.0,*41\r\n",
+        )
+        .unwrap();
+        let Datagram::Sentences(sentences) = datagram else {
+            panic!("expected sentences");
+        };
+        assert_eq!(sentences.len(), 2);
+        assert_eq!(
+            sentences[0].tag_block().unwrap().source.as_deref(),
+            Some("GP0001")
+        );
+
+        let Datagram::Sentences(sentences) =
+            parse_datagram(b"UdPbC\0$GPDPT,87.4,0.0,*40\r\n$GPDPT,87.5,0.0,*41").unwrap()
+        else {
+            panic!("expected sentences");
+        };
+        assert_eq!(sentences.len(), 2);
+        assert_eq!(sentences[1].as_bytes(), b"$GPDPT,87.5,0.0,*41\r\n");
+
+        assert!(matches!(
+            parse_datagram(b"RaUdP\0\x01\x02").unwrap(),
+            Datagram::BinaryImage(_)
+        ));
+        assert_eq!(
+            parse_datagram(b"$GPDPT,87.4,0.0,*40\r\n").unwrap_err(),
+            ParseError::BadDatagramHeader
+        );
+        assert_eq!(
+            MulticastGroup::NAVD.address().to_string(),
+            "239.192.0.4:60004"
+        );
+    }
+
+    #[test]
+    fn test_loopback_multicast() {
+        let receiver = Receiver::join(MulticastGroup::USR8, Ipv4Addr::LOCALHOST).unwrap();
+        receiver
+            .socket()
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        let mut sender = Sender::new("GP0001", MulticastGroup::USR8, Ipv4Addr::LOCALHOST).unwrap();
+
+        let sentence = NMEASentence::from_bytes(b"$GPDPT,87.4,0.0,*40\r\n").unwrap();
+        sender.send(&sentence).unwrap();
+
+        let Datagram::Sentences(sentences) = receiver.recv().unwrap() else {
+            panic!("expected sentences");
+        };
+        assert_eq!(sentences[0].as_bytes(), sentence.as_bytes());
+        let tag_block = sentences[0].tag_block().unwrap();
+        assert_eq!(tag_block.source.as_deref(), Some("GP0001"));
+        assert_eq!(tag_block.line_count, Some(1));
+    }
+}
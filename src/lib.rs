@@ -0,0 +1,73 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+//! NMEA 0183 / IEC 61162-1 parser.\
+//! Without the default `std` feature the crate is `no_std`: framing of byte streams with
+//! the fixed capacity [SentenceFramer] or of borrowed buffers with [SentenceRef], checksums,
+//! address decoding, [Fields] and the formatters need neither `std` nor an allocator. The `alloc` feature adds owned sentences, the push
+//! [NMEADecoder](crate::NMEADecoder), tag blocks and escape decoding.
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod approved_sentence_formatters;
+#[cfg(feature = "parallel")]
+mod bulk;
+#[cfg(feature = "tokio")]
+mod codec;
+#[cfg(feature = "alloc")]
+mod decoder;
+#[cfg(feature = "alloc")]
+mod encoder;
+mod error;
+mod fields;
+mod framer;
+#[cfg(all(feature = "std", any(test, fuzzing)))]
+#[doc(hidden)]
+pub mod fuzzing;
+#[cfg(feature = "std")]
+pub mod iec61162_450;
+pub mod primitives;
+#[cfg(feature = "alloc")]
+pub mod proprietary_sentence_formatters;
+mod query;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "alloc")]
+mod reassembly;
+mod sentence;
+mod sentence_ref;
+#[cfg(feature = "alloc")]
+mod tag_block;
+mod talker;
+
+#[cfg(feature = "parallel")]
+pub use crate::bulk::{map_file, BulkParser};
+#[cfg(feature = "tokio")]
+pub use crate::codec::{AsyncNMEASentenceReader, NMEACodec};
+#[cfg(feature = "alloc")]
+pub use crate::decoder::{DecodeEvent, DiscardReason, FramingPolicy, FramingStats, NMEADecoder};
+#[cfg(feature = "alloc")]
+pub use crate::encoder::{Encode, FieldWriter, ToField};
+pub use crate::error::ParseError;
+pub use crate::fields::{Fields, FromField};
+pub use crate::framer::SentenceFramer;
+pub use crate::query::Query;
+#[cfg(feature = "alloc")]
+pub use crate::query::QueryResponder;
+#[cfg(feature = "std")]
+pub use crate::reader::NMEASentenceReader;
+#[cfg(feature = "alloc")]
+pub use crate::reassembly::{
+    AssembledMessage, FragmentLayout, MessageKey, Reassembler, ReassemblyStats,
+    DEFAULT_REASSEMBLY_TIMEOUT,
+};
+pub use crate::sentence::{
+    Address, ChecksumPolicy, ChecksumStatus, NMEAAddressField, NMEAAddressFieldType,
+    NMEAApprovedAddressField, NMEAManufacturerCode, NMEAProprietaryAddressField,
+    NMEAQueryAddressField, NMEASentenceFormatter, SentenceType, NMEA_SENTENCE_MAX_LENGTH,
+};
+#[cfg(feature = "alloc")]
+pub use crate::sentence::{NMEADateContent, NMEASentence};
+pub use crate::sentence_ref::{FieldsRef, SentenceRef, NMEA_MAX_FIELDS};
+#[cfg(feature = "alloc")]
+pub use crate::tag_block::{SentenceGroup, TagBlock, TAG_BLOCK_MAX_LENGTH};
+pub use crate::talker::{Constellation, Talker, UnknownTalker};
@@ -1,7 +1,6 @@
-use std::error::Error;
-use std::fmt;
-use std::num::{ParseFloatError, ParseIntError};
-use std::string::ParseError;
+use core::error::Error;
+use core::fmt;
+use core::num::{ParseFloatError, ParseIntError};
 
 #[derive(Debug)]
 pub enum CoordinateError {
@@ -9,6 +8,8 @@ pub enum CoordinateError {
     ParseFloatError(ParseFloatError),
     InvalidDirection(char),
     InvalidLength(char),
+    /// Decimal degrees that are not finite or beyond ±90 / ±180
+    OutOfRange,
 }
 
 impl fmt::Display for CoordinateError {
@@ -18,6 +19,7 @@ impl fmt::Display for CoordinateError {
             CoordinateError::ParseFloatError(e) => write!(f, "Failed to parse float: {}", e),
             CoordinateError::InvalidDirection(c) => write!(f, "Invalid direction: {}", c),
             CoordinateError::InvalidLength(c) => write!(f, "Invalid length: {}", c),
+            CoordinateError::OutOfRange => write!(f, "Coordinate out of range"),
         }
     }
 }
@@ -36,6 +38,7 @@ impl From<ParseFloatError> for CoordinateError {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Coordinate {
     degrees: u16,
     minutes: f32,
@@ -48,6 +51,12 @@ impl Default for Coordinate {
     }
 }
 
+impl fmt::Display for Coordinate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}° {}' {}", self.degrees, self.minutes, self.direction)
+    }
+}
+
 impl Coordinate {
     pub(crate) fn new(degrees: u16, minutes: f32, direction: char) -> Self {
         Coordinate {
@@ -57,6 +66,38 @@ impl Coordinate {
         }
     }
 
+    /// Latitude from signed decimal degrees, negative values are south
+    pub fn from_latitude_degrees(degrees: f64) -> Result<Self, CoordinateError> {
+        Self::from_decimal_degrees(degrees, 90.0, ['N', 'S'])
+    }
+
+    /// Longitude from signed decimal degrees, negative values are west
+    pub fn from_longitude_degrees(degrees: f64) -> Result<Self, CoordinateError> {
+        Self::from_decimal_degrees(degrees, 180.0, ['E', 'W'])
+    }
+
+    fn from_decimal_degrees(
+        value: f64,
+        limit: f64,
+        [positive, negative]: [char; 2],
+    ) -> Result<Self, CoordinateError> {
+        // NaN fails every comparison and ends up here as well
+        if !(-limit..=limit).contains(&value) {
+            return Err(CoordinateError::OutOfRange);
+        }
+        let (magnitude, direction) = if value < 0.0 {
+            (-value, negative)
+        } else {
+            (value, positive)
+        };
+        let degrees = magnitude as u16;
+        Ok(Coordinate {
+            degrees,
+            minutes: ((magnitude - degrees as f64) * 60.0) as f32,
+            direction,
+        })
+    }
+
     pub fn to_decimal_degrees(&self) -> f64 {
         let mut decimal_degrees = self.degrees as f64 + (self.minutes as f64 / 60.0);
         if self.direction == 'S' || self.direction == 'W' {
@@ -65,16 +106,27 @@ impl Coordinate {
         decimal_degrees
     }
 
-    pub fn to_string(&self) -> String {
-        format!("{}° {}' {}", self.degrees, self.minutes, self.direction)
+    pub fn degrees(&self) -> u16 {
+        self.degrees
+    }
+
+    pub fn minutes(&self) -> f32 {
+        self.minutes
     }
 
-    pub fn from_latitude_string(coord: String, direction: char) -> Result<Self, CoordinateError> {
+    pub fn direction(&self) -> char {
+        self.direction
+    }
+
+    pub fn from_latitude_string(coord: &str, direction: char) -> Result<Self, CoordinateError> {
         if coord.len() < 4 {
             return Err(CoordinateError::InvalidLength(direction));
         }
-        let degrees = coord[..2].parse::<u16>()?;
-        let minutes = coord[2..].parse::<f32>()?;
+        let (degrees, minutes) = coord
+            .split_at_checked(2)
+            .ok_or(CoordinateError::InvalidLength(direction))?;
+        let degrees = degrees.parse::<u16>()?;
+        let minutes = minutes.parse::<f32>()?;
         if direction != 'N' && direction != 'S' {
             return Err(CoordinateError::InvalidDirection(direction));
         }
@@ -85,12 +137,15 @@ impl Coordinate {
         })
     }
 
-    pub fn from_longitude_string(coord: String, direction: char) -> Result<Self, CoordinateError> {
+    pub fn from_longitude_string(coord: &str, direction: char) -> Result<Self, CoordinateError> {
         if coord.len() < 5 {
             return Err(CoordinateError::InvalidLength(direction));
         }
-        let degrees = coord[..3].parse::<u16>()?;
-        let minutes = coord[3..].parse::<f32>()?;
+        let (degrees, minutes) = coord
+            .split_at_checked(3)
+            .ok_or(CoordinateError::InvalidLength(direction))?;
+        let degrees = degrees.parse::<u16>()?;
+        let minutes = minutes.parse::<f32>()?;
         if direction != 'E' && direction != 'W' {
             return Err(CoordinateError::InvalidDirection(direction));
         }
@@ -101,3 +156,27 @@ impl Coordinate {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_decimal_degrees() {
+        let latitude = Coordinate::from_latitude_degrees(-48.1173).unwrap();
+        assert_eq!((latitude.degrees(), latitude.direction()), (48, 'S'));
+        assert!((latitude.minutes() - 7.038).abs() < 1e-3);
+        assert!((latitude.to_decimal_degrees() + 48.1173).abs() < 1e-6);
+
+        let longitude = Coordinate::from_longitude_degrees(180.0).unwrap();
+        assert_eq!((longitude.degrees(), longitude.direction()), (180, 'E'));
+
+        for invalid in [f64::NAN, f64::INFINITY, 90.5] {
+            assert!(matches!(
+                Coordinate::from_latitude_degrees(invalid),
+                Err(CoordinateError::OutOfRange)
+            ));
+        }
+        assert!(Coordinate::from_longitude_degrees(-180.1).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::error::ParseError;
+use crate::sentence::parse_hex_byte;
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+/// Characters that may not appear literally inside a field, IEC 61162-1 table 1.
+/// Control characters and DEL are not allowed either, see [is_reserved].
+pub const RESERVED_CHARACTERS: &[u8] = b"\r\n$*,!\\^~";
+const ESCAPE: u8 = b'^';
+const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+/// `byte` has to be escaped inside a field: a reserved character, a control character or DEL
+pub fn is_reserved(byte: u8) -> bool {
+    RESERVED_CHARACTERS.contains(&byte) || byte.is_ascii_control()
+}
+
+/// Replaces `^hh` escapes in a field with the character they stand for.\
+/// Fields without escapes are returned as is without copying.
+pub fn decode_escaped(field: &[u8]) -> Result<Cow<'_, [u8]>, ParseError> {
+    if !field.contains(&ESCAPE) {
+        return Ok(Cow::Borrowed(field));
+    }
+    let mut decoded = Vec::with_capacity(field.len());
+    let mut offset = 0;
+    while offset < field.len() {
+        if field[offset] == ESCAPE {
+            let byte = field
+                .get(offset + 1..offset + 3)
+                .and_then(parse_hex_byte)
+                .ok_or(ParseError::BadEscape { offset })?;
+            decoded.push(byte);
+            offset += 3;
+        } else {
+            decoded.push(field[offset]);
+            offset += 1;
+        }
+    }
+    Ok(Cow::Owned(decoded))
+}
+
+/// Escapes reserved characters as `^hh` so the field can be placed in an outgoing sentence.
+pub fn encode_escaped(field: &[u8]) -> Cow<'_, [u8]> {
+    if !field.iter().any(|byte| is_reserved(*byte)) {
+        return Cow::Borrowed(field);
+    }
+    let mut encoded = Vec::with_capacity(field.len() + 8);
+    for byte in field {
+        if is_reserved(*byte) {
+            encoded.extend_from_slice(&[
+                ESCAPE,
+                HEX_DIGITS[(byte >> 4) as usize],
+                HEX_DIGITS[(byte & 0xF) as usize],
+            ]);
+        } else {
+            encoded.push(*byte);
+        }
+    }
+    Cow::Owned(encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_round_trip() {
+        let text = b"DEPTH ALARM, 5.0 m ^ *critical*";
+
+        let encoded = encode_escaped(text);
+        assert_eq!(&*encoded, b"DEPTH ALARM^2C 5.0 m ^5E ^2Acritical^2A");
+        assert_eq!(&*decode_escaped(&encoded).unwrap(), text);
+        assert_eq!(&*encode_escaped(b"\tA\x7F"), b"^09A^7F");
+
+        assert!(matches!(
+            decode_escaped(b"plain text").unwrap(),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(
+            decode_escaped(b"ALARM^2").unwrap_err(),
+            ParseError::BadEscape { offset: 5 }
+        );
+        for invalid in [&b"^+1"[..], b"^-1", b"^ 1", b"^1G"] {
+            assert_eq!(
+                decode_escaped(invalid).unwrap_err(),
+                ParseError::BadEscape { offset: 0 }
+            );
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use core::fmt;
+use core::ops::Deref;
+
+/// Up to `N` values stored inline so no allocator is needed, dereferences to `[T]`.\
+/// Used for the repeated fields of a sentence, e.g. the satellite ids of `GSA`.
+#[derive(Clone, Copy)]
+pub struct InlineArray<T, const N: usize> {
+    values: [T; N],
+    length: usize,
+}
+
+impl<T: Copy + Default, const N: usize> InlineArray<T, N> {
+    pub fn new() -> Self {
+        InlineArray {
+            values: [T::default(); N],
+            length: 0,
+        }
+    }
+
+    /// Appends `value`, handing it back if all `N` places are taken
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.length == N {
+            return Err(value);
+        }
+        self.values[self.length] = value;
+        self.length += 1;
+        Ok(())
+    }
+}
+
+impl<T: Copy + Default, const N: usize> Default for InlineArray<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Deref for InlineArray<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.values[..self.length]
+    }
+}
+
+/// Values beyond the `N`th are dropped
+impl<T: Copy + Default, const N: usize> FromIterator<T> for InlineArray<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut array = Self::new();
+        for value in iter.into_iter().take(N) {
+            let _ = array.push(value);
+        }
+        array
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for InlineArray<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for InlineArray<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for InlineArray<T, N> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inline_array() {
+        let mut array: InlineArray<u8, 2> = [1, 2, 3].into_iter().collect();
+        assert_eq!(&*array, &[1, 2]);
+        assert_eq!(array.push(4), Err(4));
+
+        let mut other = InlineArray::<u8, 2>::new();
+        assert!(other.is_empty());
+        other.push(1).unwrap();
+        assert_ne!(array, other);
+        other.push(2).unwrap();
+        assert_eq!(array, other);
+        array = InlineArray::default();
+        assert_eq!(format!("{:?}", array), "[]");
+    }
+}
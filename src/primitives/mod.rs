@@ -1 +1,4 @@
 pub mod coordinates;
+#[cfg(feature = "alloc")]
+pub mod escape;
+pub mod inline_array;
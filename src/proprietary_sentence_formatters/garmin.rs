@@ -0,0 +1,86 @@
+use crate::approved_sentence_formatters::CustomSentence;
+use crate::error::ParseError;
+use crate::proprietary_sentence_formatters::ProprietaryParser;
+use crate::Fields;
+use alloc::{boxed::Box, vec::Vec};
+use core::any::Any;
+
+/// Built-in parser for Garmin `$PGRM` sentences
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GarminParser;
+
+impl ProprietaryParser for GarminParser {
+    fn parse(&self, content: &[u8]) -> Result<Option<Box<dyn CustomSentence>>, ParseError> {
+        let fields: Vec<&[u8]> = content.split(|c| *c == b',').collect();
+        Ok(match fields[0] {
+            b"E" => Some(Box::new(PGRME::from_field(&fields)?)),
+            _ => None,
+        })
+    }
+}
+
+/// Estimated position error
+#[derive(Debug, Clone, PartialEq)]
+pub struct PGRME {
+    pub horizontal_error: Option<f32>, // Estimated horizontal position error, meters
+    pub vertical_error: Option<f32>,   // Estimated vertical position error, meters
+    pub position_error: Option<f32>,   // Estimated overall spherical position error, meters
+}
+
+impl PGRME {
+    /// `fields[0]` is the sentence id `E`, each value is followed by its unit `M`
+    pub fn from_field(fields: &[&[u8]]) -> Result<Self, ParseError> {
+        if fields.len() < 7 {
+            return Err(ParseError::MissingField(fields.len()));
+        }
+        Ok(Self {
+            horizontal_error: fields.f32(1)?,
+            vertical_error: fields.f32(3)?,
+            position_error: fields.f32(5)?,
+        })
+    }
+}
+
+impl CustomSentence for PGRME {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approved_sentence_formatters::SentenceContent;
+    use crate::proprietary_sentence_formatters::{ProprietaryContent, ProprietaryRegistry};
+    use crate::SentenceRef;
+
+    #[test]
+    fn test_parse_pgrme() {
+        let sentence = SentenceRef::parse(b"$PGRME,15.0,M,45.0,M,25.0,M*1C\r\n").unwrap();
+        let content = ProprietaryRegistry::new()
+            .parse_sentence(&sentence)
+            .unwrap();
+        let SentenceContent::Proprietary(ProprietaryContent::Parsed { sentence, .. }) = content
+        else {
+            panic!("PGRME not parsed");
+        };
+        assert_eq!(
+            sentence.downcast_ref::<PGRME>(),
+            Some(&PGRME {
+                horizontal_error: Some(15.0),
+                vertical_error: Some(45.0),
+                position_error: Some(25.0),
+            })
+        );
+        assert!((*sentence).as_any().is::<PGRME>());
+
+        assert_eq!(
+            GarminParser.parse(b"E,15.0,M,4x,M,25.0,M").unwrap_err(),
+            ParseError::UnparsableField {
+                index: 3,
+                offset: 9
+            }
+        );
+        assert!(GarminParser.parse(b"Z,246,f,3").unwrap().is_none());
+    }
+}
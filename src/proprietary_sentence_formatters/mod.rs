@@ -0,0 +1,176 @@
+use crate::approved_sentence_formatters::{CustomSentence, SentenceContent};
+use crate::error::ParseError;
+use crate::proprietary_sentence_formatters::garmin::GarminParser;
+use crate::sentence::{Address, NMEAManufacturerCode};
+use crate::sentence_ref::SentenceRef;
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+use core::fmt;
+
+pub mod garmin;
+
+/// Decodes the sentences of one manufacturer, see [ProprietaryRegistry::register].
+pub trait ProprietaryParser: Send + Sync {
+    /// `content` is everything after the manufacturer code up to the checksum, e.g. `E,15.0,M`
+    /// for `$PGRME,15.0,M`. `Ok(None)` leaves sentences the parser does not know as
+    /// [ProprietaryContent::Unknown].
+    fn parse(&self, content: &[u8]) -> Result<Option<Box<dyn CustomSentence>>, ParseError>;
+}
+
+impl<F> ProprietaryParser for F
+where
+    F: Fn(&[u8]) -> Result<Option<Box<dyn CustomSentence>>, ParseError> + Send + Sync,
+{
+    fn parse(&self, content: &[u8]) -> Result<Option<Box<dyn CustomSentence>>, ParseError> {
+        self(content)
+    }
+}
+
+#[derive(Debug)]
+pub enum ProprietaryContent {
+    Parsed {
+        manufacturer: NMEAManufacturerCode,
+        sentence: Box<dyn CustomSentence>,
+    },
+    /// No parser knows the sentence, `content` is passed through unchanged
+    Unknown {
+        manufacturer: NMEAManufacturerCode,
+        content: Vec<u8>,
+    },
+}
+
+impl ProprietaryContent {
+    pub fn manufacturer(&self) -> NMEAManufacturerCode {
+        match self {
+            ProprietaryContent::Parsed { manufacturer, .. }
+            | ProprietaryContent::Unknown { manufacturer, .. } => *manufacturer,
+        }
+    }
+}
+
+/// Parsers for proprietary `$P` sentences keyed by manufacturer code.\
+/// [ProprietaryRegistry::new] starts with the built-in parsers, currently Garmin (`GRM`).
+pub struct ProprietaryRegistry {
+    parsers: BTreeMap<NMEAManufacturerCode, Box<dyn ProprietaryParser>>,
+}
+
+impl Default for ProprietaryRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ProprietaryRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.parsers.keys()).finish()
+    }
+}
+
+impl ProprietaryRegistry {
+    pub fn new() -> Self {
+        Self::empty().with_parser(['G', 'R', 'M'], GarminParser)
+    }
+
+    /// Registry without any parser, every sentence is kept as [ProprietaryContent::Unknown]
+    pub fn empty() -> Self {
+        ProprietaryRegistry {
+            parsers: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_parser(
+        mut self,
+        manufacturer: NMEAManufacturerCode,
+        parser: impl ProprietaryParser + 'static,
+    ) -> Self {
+        self.register(manufacturer, parser);
+        self
+    }
+
+    /// Adds `parser` for `manufacturer`, replacing any parser registered before
+    pub fn register(
+        &mut self,
+        manufacturer: NMEAManufacturerCode,
+        parser: impl ProprietaryParser + 'static,
+    ) {
+        self.parsers.insert(manufacturer, Box::new(parser));
+    }
+
+    pub fn parse(
+        &self,
+        manufacturer: NMEAManufacturerCode,
+        content: &[u8],
+    ) -> Result<ProprietaryContent, ParseError> {
+        let parsed = match self.parsers.get(&manufacturer) {
+            Some(parser) => parser.parse(content)?,
+            None => None,
+        };
+        Ok(match parsed {
+            Some(sentence) => ProprietaryContent::Parsed {
+                manufacturer,
+                sentence,
+            },
+            None => ProprietaryContent::Unknown {
+                manufacturer,
+                content: Vec::from(content),
+            },
+        })
+    }
+
+    /// Like [FormatterRegistry::select_sentence_formatter](crate::approved_sentence_formatters::FormatterRegistry::select_sentence_formatter)
+    /// for proprietary sentences, anything else fails with [ParseError::UnexpectedSentenceType].
+    pub fn parse_sentence(
+        &self,
+        sentence: &SentenceRef<'_>,
+    ) -> Result<SentenceContent, ParseError> {
+        let Address::Proprietary(address) = sentence.address().address else {
+            return Err(ParseError::UnexpectedSentenceType(sentence.sentence_type()));
+        };
+        self.parse(address.manufacturer, sentence.content())
+            .map(SentenceContent::Proprietary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proprietary_sentence_formatters::garmin::PGRME;
+    use core::any::Any;
+
+    #[derive(Debug, PartialEq)]
+    struct Version(u8);
+
+    impl CustomSentence for Version {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry =
+            ProprietaryRegistry::new().with_parser(['A', 'C', 'M'], |content: &[u8]| {
+                Ok(match content {
+                    [b'V', b',', version] => Some(Box::new(Version(version - b'0')) as Box<_>),
+                    _ => None,
+                })
+            });
+
+        let sentence = SentenceRef::parse(b"$PACMV,7*52\r\n").unwrap();
+        let Ok(SentenceContent::Proprietary(ProprietaryContent::Parsed { sentence, .. })) =
+            registry.parse_sentence(&sentence)
+        else {
+            panic!("ACM sentence not parsed");
+        };
+        assert_eq!(sentence.downcast_ref::<Version>(), Some(&Version(7)));
+        assert!(sentence.downcast_ref::<PGRME>().is_none());
+
+        let sentence = SentenceRef::parse(b"$PUBX,00,081350.00*3E\r\n").unwrap();
+        let Ok(SentenceContent::Proprietary(content)) = registry.parse_sentence(&sentence) else {
+            panic!("UBX sentence not passed through");
+        };
+        assert_eq!(content.manufacturer(), ['U', 'B', 'X']);
+        assert!(
+            matches!(content, ProprietaryContent::Unknown { content, .. } if content == b",00,081350.00")
+        );
+    }
+}
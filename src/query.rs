@@ -0,0 +1,167 @@
+use crate::error::ParseError;
+#[cfg(feature = "alloc")]
+use crate::sentence::{calculate_checksum, NMEASentence, SentenceType};
+use crate::sentence::{Address, NMEASentenceFormatter};
+use crate::sentence_ref::SentenceRef;
+use crate::talker::Talker;
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeMap, format, vec::Vec};
+
+/// Query sentence `$ttllQ,ccc`, the device `requester` asks `listener` to transmit `formatter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Query {
+    pub requester: Talker,
+    pub listener: Talker,
+    pub formatter: NMEASentenceFormatter,
+}
+
+impl Query {
+    pub fn new(requester: Talker, listener: Talker, formatter: NMEASentenceFormatter) -> Self {
+        Query {
+            requester,
+            listener,
+            formatter,
+        }
+    }
+
+    /// Fails with [ParseError::UnexpectedSentenceType] for anything but a query.
+    pub fn from_sentence(sentence: &SentenceRef<'_>) -> Result<Self, ParseError> {
+        let Address::Query(address) = sentence.address().address else {
+            return Err(ParseError::UnexpectedSentenceType(sentence.sentence_type()));
+        };
+        match sentence.content() {
+            [first, second, third] if [first, second, third].iter().all(|c| c.is_ascii()) => {
+                Ok(Query {
+                    requester: address.talker,
+                    listener: address.listener,
+                    formatter: [*first as char, *second as char, *third as char],
+                })
+            }
+            [] => Err(ParseError::MissingField(0)),
+            _ => Err(ParseError::UnparsableField {
+                index: 0,
+                offset: 0,
+            }),
+        }
+    }
+
+    /// The query ready to be sent, with checksum and `\r\n`
+    #[cfg(feature = "alloc")]
+    pub fn to_sentence(&self) -> NMEASentence {
+        let mut characters = Vec::with_capacity(16);
+        characters.push(b'$');
+        characters.extend_from_slice(&self.requester.code());
+        characters.extend_from_slice(&self.listener.code());
+        characters.extend_from_slice(b"Q,");
+        characters.extend(self.formatter.iter().map(|c| *c as u8));
+        let checksum = calculate_checksum(&characters);
+        characters.extend_from_slice(format!("*{:02X}\r\n", checksum).as_bytes());
+        NMEASentence {
+            characters,
+            tag_block: None,
+        }
+    }
+}
+
+/// Answers queries addressed to `listener` with the latest sentence seen for the requested formatter.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct QueryResponder {
+    listener: Talker,
+    latest: BTreeMap<NMEASentenceFormatter, NMEASentence>,
+}
+
+#[cfg(feature = "alloc")]
+impl QueryResponder {
+    pub fn new(listener: Talker) -> Self {
+        QueryResponder {
+            listener,
+            latest: BTreeMap::new(),
+        }
+    }
+
+    pub fn listener(&self) -> Talker {
+        self.listener
+    }
+
+    /// Remembers an approved sentence as the answer for its formatter, other sentences are ignored.
+    pub fn update(&mut self, sentence: &NMEASentence) {
+        let Ok(sentence_ref) = sentence.as_sentence_ref() else {
+            return;
+        };
+        if sentence_ref.sentence_type() != SentenceType::PARAMETRIC {
+            return;
+        }
+        if let Address::Approved(address) = sentence_ref.address().address {
+            self.latest.insert(address.formatter, sentence.clone());
+        }
+    }
+
+    /// The cached answer, `None` if the query is meant for another device or nothing was seen yet
+    pub fn respond(&self, query: &Query) -> Option<&NMEASentence> {
+        if query.listener != self.listener {
+            return None;
+        }
+        self.latest.get(&query.formatter)
+    }
+
+    /// Updates the cache with `sentence`, or answers it if it is a query for this device.
+    pub fn handle(&mut self, sentence: &NMEASentence) -> Option<NMEASentence> {
+        let query = sentence
+            .as_sentence_ref()
+            .and_then(|sentence| Query::from_sentence(&sentence));
+        match query {
+            Ok(query) => self.respond(&query).cloned(),
+            Err(_) => {
+                self.update(sentence);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query() {
+        let query =
+            Query::from_sentence(&SentenceRef::parse(b"$ECGPQ,RMC*30\r\n").unwrap()).unwrap();
+        assert_eq!(
+            query,
+            Query::new(Talker::Ecdis, Talker::Gps, ['R', 'M', 'C'])
+        );
+        assert_eq!(query.to_sentence().as_bytes(), b"$ECGPQ,RMC*30\r\n");
+
+        assert_eq!(
+            Query::from_sentence(&SentenceRef::parse(b"$SDDPT,87.4,0.0,*40").unwrap()),
+            Err(ParseError::UnexpectedSentenceType(SentenceType::PARAMETRIC))
+        );
+    }
+
+    #[test]
+    fn test_query_responder() {
+        let mut responder = QueryResponder::new(Talker::Sounder);
+        let dpt = NMEASentence::from_bytes(b"$SDDPT,87.4,0.0,*40\r\n").unwrap();
+        let query = Query::new(
+            Talker::IntegratedInstrumentation,
+            Talker::Sounder,
+            ['D', 'P', 'T'],
+        );
+
+        assert!(responder.handle(&query.to_sentence()).is_none());
+        assert!(responder.handle(&dpt).is_none());
+        assert_eq!(
+            responder.handle(&query.to_sentence()).unwrap().as_bytes(),
+            dpt.as_bytes()
+        );
+
+        let other = Query::new(
+            Talker::IntegratedInstrumentation,
+            Talker::Gps,
+            ['D', 'P', 'T'],
+        );
+        assert!(responder.respond(&other).is_none());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::decoder::{DecodeEvent, FramingPolicy, FramingStats, NMEADecoder};
+use crate::sentence::{ChecksumPolicy, NMEASentence};
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufReader, Read};
+
+/// Reads NMEA sentences from any [Read] source.
+pub struct NMEASentenceReader<ReaderType: Read> {
+    pub buf_reader: BufReader<ReaderType>,
+    decoder: NMEADecoder,
+    events: VecDeque<DecodeEvent>,
+    finished: bool,
+}
+
+impl<ReaderType: Read> NMEASentenceReader<ReaderType> {
+    pub fn new(reader: ReaderType) -> Self {
+        NMEASentenceReader {
+            buf_reader: BufReader::new(reader),
+            decoder: NMEADecoder::new(),
+            events: VecDeque::new(),
+            finished: false,
+        }
+    }
+
+    pub fn with_framing(mut self, framing: FramingPolicy) -> Self {
+        self.decoder = self.decoder.with_framing(framing);
+        self
+    }
+
+    pub fn with_checksum_policy(mut self, checksum_policy: ChecksumPolicy) -> Self {
+        self.decoder = self.decoder.with_checksum_policy(checksum_policy);
+        self
+    }
+
+    /// See [NMEADecoder::with_max_length]
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.decoder = self.decoder.with_max_length(max_length);
+        self
+    }
+
+    pub fn framing_stats(&self) -> FramingStats {
+        self.decoder.framing_stats()
+    }
+
+    pub fn decoder(&self) -> &NMEADecoder {
+        &self.decoder
+    }
+
+    /// Next sentence or discarded byte range, `None` once the source is exhausted.
+    pub fn next_event(&mut self) -> Option<io::Result<DecodeEvent>> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Some(Ok(event));
+            }
+            let buf = match self.buf_reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Some(Err(err)),
+            };
+            if buf.is_empty() {
+                if self.finished {
+                    return None;
+                }
+                self.finished = true;
+                self.events.extend(self.decoder.finish());
+                continue;
+            }
+            let length = buf.len();
+            self.events.extend(self.decoder.feed(buf));
+            self.buf_reader.consume(length);
+        }
+    }
+}
+
+impl<ReaderType: Read> Iterator for NMEASentenceReader<ReaderType> {
+    type Item = NMEASentence;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_event()?.ok()? {
+                DecodeEvent::Sentence(sentence) => return Some(sentence),
+                DecodeEvent::Discarded { .. } => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fuzzing::decode_everything;
+    use proptest::prelude::*;
+
+    /// One sentence of the property test grammar: approved, query or proprietary, optionally
+    /// behind a tag block, with `^hh` escapes and possibly broken checksums or line ends
+    const SENTENCE: &str = concat!(
+        "(\\\\([cdgnst]:[A-Za-z0-9^,*-]{0,10},?){0,3}(\\*[0-9A-Fa-f+-]{0,2})?\\\\)?",
+        "([$!](GP|GN|GL|SD|II)(GGA|GSA|GSV|DPT|RMC|VTG|TXT|MTW)",
+        "(,([0-9A-FNSEWMKTV.+-]|\\^[0-9A-Fa-f+-]{0,2}){0,11}){0,20}",
+        "|\\$(EC|II|GP)(GP|SD|II)Q(,[A-Z]{0,4})?",
+        "|\\$P(GRM|ACM|UBX)[A-Z]?(,([0-9A-Z.]|\\^[0-9A-F]{0,2}){0,8}){0,8})",
+        "(\\*[0-9A-F]{0,2})?(\r\n|\n)?",
+    );
+
+    #[test]
+    fn test_read_sentences() {
+        let data: &[u8] =
+            b"garbage\r\n$SDDPT,87.4,0.0,*40\r\n$SDDPT,87.5,0.0,*41\r\n\r\n$SDDPT,87.6,0.0,*42\r\n";
+        let reader = NMEASentenceReader::new(data);
+
+        let sentences = reader.map(|sentence| sentence.len()).collect::<Vec<_>>();
+
+        assert_eq!(sentences, vec![21, 21, 21]);
+    }
+
+    #[test]
+    fn test_read_lenient_framing() {
+        let data: &[u8] = b"$SDDPT,87.4,0.0,*40\n$SDDPT,87.5,0.0,*41\r\n$SDDPT,87.6,0.0,*42";
+        let mut reader = NMEASentenceReader::new(data).with_framing(FramingPolicy::LENIENT);
+
+        assert_eq!(reader.by_ref().count(), 3);
+        assert_eq!(reader.framing_stats().lf_only, 1);
+        assert_eq!(reader.framing_stats().unterminated_eof, 1);
+    }
+
+    proptest! {
+        #[test]
+        fn test_decode_arbitrary_bytes(data in proptest::collection::vec(any::<u8>(), 0..512)) {
+            decode_everything(&data);
+        }
+
+        #[test]
+        fn test_decode_malformed_sentences(
+            sentences in proptest::collection::vec(SENTENCE, 1..4)
+        ) {
+            decode_everything(sentences.concat().as_bytes());
+        }
+    }
+}
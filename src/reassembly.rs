@@ -0,0 +1,320 @@
+use crate::error::ParseError;
+use crate::fields::Fields;
+use crate::sentence::{Address, NMEASentence, NMEASentenceFormatter};
+use crate::talker::Talker;
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use core::time::Duration;
+
+/// Groups not completed within this time after their first part are dropped
+pub const DEFAULT_REASSEMBLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Indices of the fields that tie the parts of a multi-sentence message together.\
+/// Every member is the zero based index of a data field, not the value found in it:
+/// `GSV` has `total: 0, number: 1` because its first field holds the number of sentences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FragmentLayout {
+    /// Index of the field holding the total number of sentences of the message
+    pub total: usize,
+    /// Index of the field holding the number of this sentence, which starts at 1
+    pub number: usize,
+    /// Index of the field holding the sequential message identifier, which tells apart
+    /// messages sent interleaved
+    pub sequence_id: Option<usize>,
+    /// Index of the field holding the radio channel of AIS and binary messages
+    pub channel: Option<usize>,
+}
+
+impl FragmentLayout {
+    /// Layout of the approved multi-sentence messages, `None` for single sentences
+    pub fn for_formatter(formatter: &NMEASentenceFormatter) -> Option<Self> {
+        let (sequence_id, channel) = match formatter {
+            ['G', 'S', 'V'] | ['R', 'T', 'E'] | ['A', 'L', 'M'] => (None, None),
+            ['T', 'X', 'T'] => (Some(2), None),
+            ['V', 'D', 'M'] | ['V', 'D', 'O'] | ['B', 'B', 'M'] => (Some(2), Some(3)),
+            ['A', 'B', 'M'] => (Some(2), Some(4)),
+            _ => return None,
+        };
+        Some(FragmentLayout {
+            total: 0,
+            number: 1,
+            sequence_id,
+            channel,
+        })
+    }
+}
+
+/// Identifies the parts of one logical message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MessageKey {
+    pub talker: Talker,
+    pub formatter: NMEASentenceFormatter,
+    pub sequence_id: Option<u32>,
+    pub channel: Option<char>,
+}
+
+/// A complete logical message, the sentences are ordered by their sentence number
+#[derive(Debug, Clone)]
+pub struct AssembledMessage {
+    pub key: MessageKey,
+    pub sentences: Vec<NMEASentence>,
+}
+
+impl AssembledMessage {
+    /// The field with `index` of every sentence joined together, e.g. the payload of a `VDM`
+    pub fn concatenated_field(&self, index: usize) -> Result<Vec<u8>, ParseError> {
+        let mut data = vec![];
+        for sentence in &self.sentences {
+            let sentence = sentence.as_sentence_ref()?;
+            let fields = sentence.fields()?;
+            data.extend_from_slice(fields.get(index).ok_or(ParseError::MissingField(index))?);
+        }
+        Ok(data)
+    }
+}
+
+/// What happened to the messages fed into a [Reassembler]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReassemblyStats {
+    pub complete: usize,
+    /// Groups dropped after [Reassembler::timeout] with parts still missing
+    pub timed_out: usize,
+    /// Incomplete groups replaced by a new message with the same key
+    pub superseded: usize,
+    /// Sentences whose total or sentence number fields are missing or out of range
+    pub invalid: usize,
+}
+
+#[derive(Debug)]
+struct Group {
+    started: Duration,
+    received: usize,
+    parts: Vec<Option<NMEASentence>>,
+}
+
+/// Collects the parts of multi-sentence messages like `GSV`, `TXT` or `VDM`.\
+/// Parts may arrive in any order, a group is emitted as soon as it is complete.
+/// Sentences without a [FragmentLayout] are emitted right away as single sentence messages.
+/// Time is supplied by the caller as the duration since an arbitrary fixed instant,
+/// e.g. the receive time of the sentence, so the reassembler works without `std`.
+#[derive(Debug)]
+pub struct Reassembler {
+    layouts: BTreeMap<NMEASentenceFormatter, FragmentLayout>,
+    groups: BTreeMap<MessageKey, Group>,
+    timeout: Duration,
+    stats: ReassemblyStats,
+}
+
+impl Default for Reassembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Reassembler {
+    pub fn new() -> Self {
+        Reassembler {
+            layouts: BTreeMap::new(),
+            groups: BTreeMap::new(),
+            timeout: DEFAULT_REASSEMBLY_TIMEOUT,
+            stats: ReassemblyStats::default(),
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Overrides [FragmentLayout::for_formatter] for `formatter`
+    pub fn with_layout(mut self, formatter: NMEASentenceFormatter, layout: FragmentLayout) -> Self {
+        self.layouts.insert(formatter, layout);
+        self
+    }
+
+    pub fn stats(&self) -> ReassemblyStats {
+        self.stats
+    }
+
+    /// Number of incomplete groups waiting for more parts
+    pub fn pending(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Adds a sentence received at `now`, returning the message it completes.
+    /// Fails for sentences that are not approved sentences or carry an invalid sentence number.
+    pub fn feed(
+        &mut self,
+        sentence: NMEASentence,
+        now: Duration,
+    ) -> Result<Option<AssembledMessage>, ParseError> {
+        self.expire(now);
+        let (key, total, number) = self.fragment(&sentence).inspect_err(|_| {
+            self.stats.invalid += 1;
+        })?;
+        if total == 1 {
+            self.stats.complete += 1;
+            return Ok(Some(AssembledMessage {
+                key,
+                sentences: vec![sentence],
+            }));
+        }
+
+        // A part seen twice or a different total means the old message will never complete
+        if let Some(group) = self.groups.get(&key) {
+            if group.parts.len() != total || group.parts[number - 1].is_some() {
+                self.groups.remove(&key);
+                self.stats.superseded += 1;
+            }
+        }
+        let group = self.groups.entry(key).or_insert_with(|| Group {
+            started: now,
+            received: 0,
+            parts: vec![None; total],
+        });
+        group.parts[number - 1] = Some(sentence);
+        group.received += 1;
+        if group.received < total {
+            return Ok(None);
+        }
+
+        let Some(group) = self.groups.remove(&key) else {
+            return Ok(None);
+        };
+        self.stats.complete += 1;
+        Ok(Some(AssembledMessage {
+            key,
+            sentences: group.parts.into_iter().flatten().collect(),
+        }))
+    }
+
+    /// Drops the groups older than [Reassembler::timeout], returns how many were dropped
+    pub fn expire(&mut self, now: Duration) -> usize {
+        let timeout = self.timeout;
+        let pending = self.groups.len();
+        self.groups
+            .retain(|_, group| now.saturating_sub(group.started) <= timeout);
+        let expired = pending - self.groups.len();
+        self.stats.timed_out += expired;
+        expired
+    }
+
+    fn fragment(&self, sentence: &NMEASentence) -> Result<(MessageKey, usize, usize), ParseError> {
+        let sentence = sentence.as_sentence_ref()?;
+        let Address::Approved(address) = sentence.address().address else {
+            return Err(ParseError::UnexpectedSentenceType(sentence.sentence_type()));
+        };
+        let mut key = MessageKey {
+            talker: address.talker,
+            formatter: address.formatter,
+            sequence_id: None,
+            channel: None,
+        };
+        let layout = self
+            .layouts
+            .get(&address.formatter)
+            .copied()
+            .or_else(|| FragmentLayout::for_formatter(&address.formatter));
+        let Some(layout) = layout else {
+            return Ok((key, 1, 1));
+        };
+
+        let fields = sentence.fields()?;
+        let total = fields.u8(layout.total)?.unwrap_or(0) as usize;
+        let number = fields.u8(layout.number)?.unwrap_or(0) as usize;
+        if total == 0 {
+            return Err(fields.unparsable(layout.total));
+        }
+        if number == 0 || number > total {
+            return Err(fields.unparsable(layout.number));
+        }
+        if let Some(index) = layout.sequence_id {
+            key.sequence_id = fields.u32(index)?;
+        }
+        if let Some(index) = layout.channel {
+            key.channel = fields.char(index)?;
+        }
+        Ok((key, total, number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentence(bytes: &[u8]) -> NMEASentence {
+        NMEASentence::from_bytes(bytes).unwrap()
+    }
+
+    #[test]
+    fn test_reassemble_out_of_order() {
+        let mut reassembler = Reassembler::new();
+        let now = Duration::from_secs(1);
+        let payload = |message: &AssembledMessage| message.concatenated_field(4).unwrap();
+
+        let second = sentence(b"!AIVDM,2,2,3,B,1@0000000000000,2\r\n");
+        let first =
+            sentence(b"!AIVDM,2,1,3,B,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf,0\r\n");
+        let other_channel =
+            sentence(b"!AIVDM,2,1,3,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf,0\r\n");
+        assert!(reassembler.feed(second, now).unwrap().is_none());
+        assert!(reassembler.feed(other_channel, now).unwrap().is_none());
+        let message = reassembler.feed(first, now).unwrap().unwrap();
+        assert_eq!(message.key.sequence_id, Some(3));
+        assert_eq!(message.key.channel, Some('B'));
+        assert_eq!(
+            payload(&message),
+            b"55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf1@0000000000000"
+        );
+        assert_eq!(reassembler.pending(), 1);
+
+        let dpt = reassembler
+            .feed(sentence(b"$SDDPT,87.4,0.0,\r\n"), now)
+            .unwrap();
+        assert_eq!(dpt.unwrap().sentences.len(), 1);
+
+        assert_eq!(
+            reassembler
+                .feed(sentence(b"$GPGSV,3,4,11,01,,,\r\n"), now)
+                .unwrap_err(),
+            ParseError::UnparsableField {
+                index: 1,
+                offset: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_incomplete_groups() {
+        let mut reassembler = Reassembler::new().with_timeout(Duration::from_secs(2));
+        let gsv1 = b"$GPGSV,2,1,08,01,40,083,46,02,17,308,41,12,07,344,39,14,22,228,45\r\n";
+        let gsv2 = b"$GPGSV,2,2,08,18,12,078,38,19,35,289,44,22,58,138,48,25,07,040,31\r\n";
+
+        reassembler.feed(sentence(gsv1), Duration::ZERO).unwrap();
+        reassembler
+            .feed(sentence(gsv1), Duration::from_secs(1))
+            .unwrap();
+        let message = reassembler
+            .feed(sentence(gsv2), Duration::from_secs(2))
+            .unwrap()
+            .unwrap();
+        assert_eq!(message.sentences[1].as_bytes(), gsv2);
+
+        reassembler
+            .feed(sentence(gsv1), Duration::from_secs(3))
+            .unwrap();
+        assert_eq!(reassembler.expire(Duration::from_secs(6)), 1);
+        assert_eq!(
+            reassembler.stats(),
+            ReassemblyStats {
+                complete: 1,
+                timed_out: 1,
+                superseded: 1,
+                invalid: 0,
+            }
+        );
+    }
+}
@@ -0,0 +1,395 @@
+use crate::error::ParseError;
+#[cfg(feature = "alloc")]
+use crate::primitives::escape::decode_escaped;
+#[cfg(feature = "alloc")]
+use crate::sentence_ref::{FieldsRef, SentenceRef};
+#[cfg(feature = "alloc")]
+use crate::tag_block::TagBlock;
+use crate::talker::Talker;
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+/// Longest sentence allowed by IEC 61162-1, including the start delimiter and `\r\n`
+pub const NMEA_SENTENCE_MAX_LENGTH: usize = 82;
+
+/// A single framed NMEA sentence, from the start delimiter up to and including `\r\n`.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct NMEASentence {
+    pub(crate) characters: Vec<u8>,
+    pub(crate) tag_block: Option<Box<TagBlock>>,
+}
+pub type NMEASentenceFormatter = [char; 3];
+pub type NMEAManufacturerCode = [char; 3];
+#[derive(Debug, Clone, Copy)]
+pub enum NMEAAddressFieldType {
+    INVALID,
+    APPROVED,
+    QUERY,
+    PROPRIETARY,
+}
+#[derive(Clone, Copy, Debug)]
+pub struct NMEAApprovedAddressField {
+    pub talker: Talker,
+    pub formatter: NMEASentenceFormatter,
+}
+/// Address of a query `$ttllQ`, `talker` asks the device `listener` for a sentence
+#[derive(Clone, Copy, Debug)]
+pub struct NMEAQueryAddressField {
+    pub listener: Talker,
+    pub talker: Talker,
+}
+#[derive(Clone, Copy, Debug)]
+pub struct NMEAProprietaryAddressField {
+    pub manufacturer: NMEAManufacturerCode,
+}
+#[derive(Debug, Clone, Copy)]
+pub enum Address {
+    Approved(NMEAApprovedAddressField),
+    Query(NMEAQueryAddressField),
+    Proprietary(NMEAProprietaryAddressField),
+}
+#[derive(Debug, Clone, Copy)]
+pub struct NMEAAddressField {
+    pub address_type: NMEAAddressFieldType,
+    pub address: Address,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SentenceType {
+    PARAMETRIC,
+    ENCAPSULATION,
+    QUERY,
+    PROPRIETARY,
+}
+
+/// Outcome of comparing the calculated checksum with the transmitted one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumStatus {
+    Valid,
+    /// `actual` is `None` when the characters after `*` are not two hex digits
+    Invalid {
+        expected: u8,
+        actual: Option<u8>,
+    },
+    /// The sentence carries no `*hh` checksum field
+    Absent,
+}
+
+/// Which checksums are acceptable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChecksumPolicy {
+    /// Every sentence must carry a matching checksum
+    Required,
+    /// Sentences without checksum are accepted, present checksums must match
+    #[default]
+    Optional,
+    /// Checksums are not checked at all
+    Ignore,
+}
+
+impl ChecksumPolicy {
+    pub fn check(&self, status: ChecksumStatus) -> Result<(), ParseError> {
+        match (self, status) {
+            (ChecksumPolicy::Ignore, _) | (_, ChecksumStatus::Valid) => Ok(()),
+            (ChecksumPolicy::Optional, ChecksumStatus::Absent) => Ok(()),
+            (ChecksumPolicy::Required, ChecksumStatus::Absent) => Err(ParseError::MissingChecksum),
+            (_, ChecksumStatus::Invalid { expected, actual }) => match actual {
+                Some(actual) => Err(ParseError::BadChecksum { expected, actual }),
+                None => Err(ParseError::MalformedChecksum),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct NMEADateContent {
+    pub sentence_type: SentenceType,
+    pub address: NMEAAddressField,
+    pub content: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl NMEASentence {
+    /// Builds a sentence from raw bytes, `None` if they exceed [NMEA_SENTENCE_MAX_LENGTH].
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::from_bytes_with_max_length(bytes, NMEA_SENTENCE_MAX_LENGTH)
+    }
+
+    /// Like [NMEASentence::from_bytes] for devices exceeding the standard length, e.g. `PUBX,00`.
+    pub fn from_bytes_with_max_length(bytes: &[u8], max_length: usize) -> Option<Self> {
+        if bytes.len() > max_length {
+            return None;
+        }
+        Some(NMEASentence {
+            characters: bytes.to_vec(),
+            tag_block: None,
+        })
+    }
+
+    pub fn with_tag_block(mut self, tag_block: TagBlock) -> Self {
+        self.tag_block = Some(Box::new(tag_block));
+        self
+    }
+
+    pub fn tag_block(&self) -> Option<&TagBlock> {
+        self.tag_block.as_deref()
+    }
+
+    pub fn set_tag_block(&mut self, tag_block: Option<TagBlock>) {
+        self.tag_block = tag_block.map(Box::new);
+    }
+
+    /// Writes the sentence prefixed with its tag block, if it has one.
+    #[cfg(feature = "std")]
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        if let Some(tag_block) = &self.tag_block {
+            write!(writer, "{}", tag_block)?;
+        }
+        writer.write_all(self.as_bytes())
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.characters
+    }
+
+    pub fn len(&self) -> usize {
+        self.characters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.characters.is_empty()
+    }
+
+    /// XOR of all characters between the start delimiter and `*` or the line terminator.
+    pub fn calculate_checksum(&self) -> u8 {
+        calculate_checksum(self.as_bytes())
+    }
+
+    /// The transmitted `*hh` checksum, `None` if the sentence has none or it is not two hex digits.
+    pub fn parse_checksum(&self) -> Option<u8> {
+        parse_checksum(self.as_bytes())
+    }
+
+    pub fn checksum_status(&self) -> ChecksumStatus {
+        checksum_status(self.as_bytes())
+    }
+
+    pub fn valid(&self) -> bool {
+        self.checksum_status() == ChecksumStatus::Valid
+    }
+
+    /// Checks the checksum against `policy`, reporting both checksums on mismatch.
+    pub fn verify_checksum(&self, policy: ChecksumPolicy) -> Result<(), ParseError> {
+        policy.check(self.checksum_status())
+    }
+
+    /// Borrowed view of this sentence, see [SentenceRef]
+    pub fn as_sentence_ref(&self) -> Result<SentenceRef<'_>, ParseError> {
+        SentenceRef::parse(self.as_bytes())
+    }
+
+    pub fn decode(&self) -> Result<NMEADateContent, ParseError> {
+        let sentence = self.as_sentence_ref()?;
+        Ok(NMEADateContent {
+            sentence_type: sentence.sentence_type(),
+            address: sentence.address(),
+            content: Vec::from(sentence.content()),
+        })
+    }
+}
+
+pub(crate) fn calculate_checksum(characters: &[u8]) -> u8 {
+    characters
+        .iter()
+        .skip(1)
+        .take_while(|&c| *c != b'*' && *c != b'\r' && *c != b'\n')
+        .fold(0, |checksum, c| checksum ^ *c)
+}
+
+pub(crate) fn parse_checksum(characters: &[u8]) -> Option<u8> {
+    let start = characters.iter().position(|c| *c == b'*')? + 1;
+    let checksum = characters.get(start..start + 2)?;
+    if characters[start + 2..]
+        .iter()
+        .any(|c| *c != b'\r' && *c != b'\n')
+    {
+        return None;
+    }
+    parse_hex_byte(checksum)
+}
+
+/// Exactly two hex digits, `from_str_radix` alone would also accept a sign like `+1`
+pub(crate) fn parse_hex_byte(hex: &[u8]) -> Option<u8> {
+    match hex {
+        [high, low] if high.is_ascii_hexdigit() && low.is_ascii_hexdigit() => {
+            u8::from_str_radix(core::str::from_utf8(hex).ok()?, 16).ok()
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn checksum_status(characters: &[u8]) -> ChecksumStatus {
+    if !characters.contains(&b'*') {
+        return ChecksumStatus::Absent;
+    }
+    let expected = calculate_checksum(characters);
+    match parse_checksum(characters) {
+        Some(actual) if actual == expected => ChecksumStatus::Valid,
+        actual => ChecksumStatus::Invalid { expected, actual },
+    }
+}
+
+pub(crate) fn decode_approved_address(characters: &[u8]) -> NMEAAddressField {
+    let talker = Talker::from_code([characters[1], characters[2]]);
+    let formatter = [
+        characters[3] as char,
+        characters[4] as char,
+        characters[5] as char,
+    ];
+    NMEAAddressField {
+        address_type: NMEAAddressFieldType::APPROVED,
+        address: Address::Approved(NMEAApprovedAddressField { talker, formatter }),
+    }
+}
+
+pub(crate) fn decode_query_address(characters: &[u8]) -> NMEAAddressField {
+    let talker = Talker::from_code([characters[1], characters[2]]);
+    let listener = Talker::from_code([characters[3], characters[4]]);
+    NMEAAddressField {
+        address_type: NMEAAddressFieldType::QUERY,
+        address: Address::Query(NMEAQueryAddressField { listener, talker }),
+    }
+}
+
+/// `$Pmmm`, the content of the sentence starts right after the manufacturer code `mmm`
+pub(crate) fn decode_proprietary_address(characters: &[u8]) -> NMEAAddressField {
+    let manufacturer = [
+        characters[2] as char,
+        characters[3] as char,
+        characters[4] as char,
+    ];
+    NMEAAddressField {
+        address_type: NMEAAddressFieldType::PROPRIETARY,
+        address: Address::Proprietary(NMEAProprietaryAddressField { manufacturer }),
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl NMEADateContent {
+    pub fn parse_content_fields(&mut self) -> Vec<&[u8]> {
+        self.content.split(|&x| x == b',').collect()
+    }
+
+    pub fn fields(&self) -> Result<FieldsRef<'_>, ParseError> {
+        FieldsRef::new(&self.content)
+    }
+
+    /// The field with `index` with all `^hh` escapes resolved
+    pub fn decoded_field(&self, index: usize) -> Result<Cow<'_, [u8]>, ParseError> {
+        let fields = self.fields()?;
+        let field = fields.get(index).ok_or(ParseError::MissingField(index))?;
+        decode_escaped(field).map_err(|_| ParseError::unparsable_field(&fields, index))
+    }
+
+    /// Like [NMEADateContent::decoded_field] for text fields as in `TXT` or `ALR`
+    pub fn text_field(&self, index: usize) -> Result<String, ParseError> {
+        Ok(self
+            .decoded_field(index)?
+            .iter()
+            .map(|byte| *byte as char)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_content() {
+        let nmea_sentence = "$GPGSA,A,3,32,21,22,01,03,31,04,17,08,71,72,,1.50,0.90,1.20*07";
+        let sentence = NMEASentence {
+            characters: nmea_sentence.as_bytes().to_vec(),
+            tag_block: None,
+        };
+
+        let mut data = sentence.decode().unwrap();
+        let parsed_content = data.parse_content_fields();
+
+        let expected_content: Vec<&[u8]> = vec![
+            b"A", b"3", b"32", b"21", b"22", b"01", b"03", b"31", b"04", b"17", b"08", b"71",
+            b"72", b"", b"1.50", b"0.90", b"1.20",
+        ];
+
+        assert_eq!(parsed_content, expected_content);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let sentence = NMEASentence::from_bytes(b"$GPG").unwrap();
+        assert_eq!(sentence.decode().unwrap_err(), ParseError::TooShort(4));
+
+        let sentence = NMEASentence::from_bytes(b"#GPDPT,87.4,0.0,*4B\r\n").unwrap();
+        assert_eq!(
+            sentence.decode().unwrap_err(),
+            ParseError::BadStartDelimiter(b'#')
+        );
+
+        let sentence = NMEASentence::from_bytes(b"$GPDPT,87.4,0.0,*4C\r\n").unwrap();
+        assert_eq!(
+            sentence
+                .verify_checksum(ChecksumPolicy::Optional)
+                .unwrap_err(),
+            ParseError::BadChecksum {
+                expected: 0x40,
+                actual: 0x4C
+            }
+        );
+        assert_eq!(sentence.decode().unwrap().content, b"87.4,0.0,");
+    }
+
+    #[test]
+    fn test_checksum_status() {
+        let sentence = NMEASentence::from_bytes(b"$GPDPT,87.4,0.0,*40\r\n").unwrap();
+        assert_eq!(sentence.checksum_status(), ChecksumStatus::Valid);
+
+        let sentence = NMEASentence::from_bytes(b"$GPDPT,87.4,0.0,*4X\r\n").unwrap();
+        assert_eq!(
+            sentence.checksum_status(),
+            ChecksumStatus::Invalid {
+                expected: 0x40,
+                actual: None
+            }
+        );
+        assert_eq!(sentence.verify_checksum(ChecksumPolicy::Ignore), Ok(()));
+
+        let sentence = NMEASentence::from_bytes(b"$GPDPT,87.4,0.0,*+0\r\n").unwrap();
+        assert_eq!(
+            sentence.verify_checksum(ChecksumPolicy::Required),
+            Err(ParseError::MalformedChecksum)
+        );
+
+        let sentence = NMEASentence::from_bytes(b"$GPDPT,87.4,0.0,\r\n").unwrap();
+        assert_eq!(sentence.checksum_status(), ChecksumStatus::Absent);
+        assert_eq!(sentence.verify_checksum(ChecksumPolicy::Optional), Ok(()));
+        assert_eq!(
+            sentence.verify_checksum(ChecksumPolicy::Required),
+            Err(ParseError::MissingChecksum)
+        );
+    }
+
+    #[test]
+    fn test_text_field() {
+        let sentence =
+            NMEASentence::from_bytes(b"$GPTXT,01,01,02,ANTENNA OPEN^2C CHECK ^5ECABLE^5E*4B\r\n")
+                .unwrap();
+        let data = sentence.decode().unwrap();
+
+        assert_eq!(data.text_field(3).unwrap(), "ANTENNA OPEN, CHECK ^CABLE^");
+        assert_eq!(data.text_field(4), Err(ParseError::MissingField(4)));
+    }
+}
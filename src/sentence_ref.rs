@@ -0,0 +1,229 @@
+use crate::error::ParseError;
+use crate::sentence::{
+    checksum_status, decode_approved_address, decode_proprietary_address, decode_query_address,
+    ChecksumStatus, NMEAAddressField, SentenceType,
+};
+#[cfg(feature = "alloc")]
+use crate::{sentence::NMEASentence, tag_block::TagBlock};
+use core::ops::Deref;
+
+/// Most fields a [FieldsRef] can hold, content with more fields fails with [ParseError::TooManyFields].\
+/// A sentence of [NMEA_SENTENCE_MAX_LENGTH](crate::NMEA_SENTENCE_MAX_LENGTH) has at most 74 fields,
+/// only sentences accepted with a raised maximum length can go beyond the limit.
+pub const NMEA_MAX_FIELDS: usize = 96;
+
+/// Borrowed view of a sentence, parsed in place without copying the input buffer.
+#[derive(Debug, Clone, Copy)]
+pub struct SentenceRef<'a> {
+    characters: &'a [u8],
+    tag_block: Option<&'a [u8]>,
+    sentence_type: SentenceType,
+    address: NMEAAddressField,
+    content: &'a [u8],
+}
+
+impl<'a> SentenceRef<'a> {
+    /// Parses a single sentence, optionally preceded by a tag block and followed by a line terminator.
+    pub fn parse(data: &'a [u8]) -> Result<Self, ParseError> {
+        let terminator = data
+            .iter()
+            .rev()
+            .take_while(|c| **c == b'\r' || **c == b'\n')
+            .count();
+        let mut characters = &data[..data.len() - terminator];
+        let mut tag_block = None;
+        if let Some(rest) = characters.strip_prefix(b"\\") {
+            let end = rest
+                .iter()
+                .position(|c| *c == b'\\')
+                .ok_or(ParseError::BadTagBlock)?;
+            tag_block = Some(&rest[..end]);
+            characters = &rest[end + 1..];
+        }
+        if characters.len() <= 5 {
+            return Err(ParseError::TooShort(characters.len()));
+        }
+
+        let (sentence_type, address, content_start) = match characters[0] {
+            b'!' => (
+                SentenceType::ENCAPSULATION,
+                decode_approved_address(characters),
+                7,
+            ),
+            b'$' => match characters[1] {
+                b'P' => (
+                    SentenceType::PROPRIETARY,
+                    decode_proprietary_address(characters),
+                    5,
+                ),
+                _ => match characters[5] {
+                    b'Q' => (SentenceType::QUERY, decode_query_address(characters), 7),
+                    _ => (
+                        SentenceType::PARAMETRIC,
+                        decode_approved_address(characters),
+                        7,
+                    ),
+                },
+            },
+            byte => return Err(ParseError::BadStartDelimiter(byte)),
+        };
+        let content_end = characters
+            .iter()
+            .position(|c| *c == b'*')
+            .unwrap_or(characters.len());
+        let content = characters
+            .get(content_start..content_end)
+            .unwrap_or_default();
+
+        Ok(SentenceRef {
+            characters,
+            tag_block,
+            sentence_type,
+            address,
+            content,
+        })
+    }
+
+    /// Iterates over all sentences of a buffer holding one sentence per line, empty lines are skipped.
+    pub fn parse_all(
+        buffer: &'a [u8],
+    ) -> impl Iterator<Item = Result<SentenceRef<'a>, ParseError>> {
+        buffer
+            .split(|c| *c == b'\n')
+            .filter(|line| line.iter().any(|c| *c != b'\r'))
+            .map(SentenceRef::parse)
+    }
+
+    /// The sentence from its start delimiter up to the checksum, without tag block and line terminator
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.characters
+    }
+
+    /// Raw tag block parameters between the `\` delimiters, see [SentenceRef::tag_block]
+    pub fn tag_block_bytes(&self) -> Option<&'a [u8]> {
+        self.tag_block
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn tag_block(&self) -> Option<Result<TagBlock, ParseError>> {
+        self.tag_block.map(TagBlock::parse)
+    }
+
+    pub fn sentence_type(&self) -> SentenceType {
+        self.sentence_type
+    }
+
+    pub fn address(&self) -> NMEAAddressField {
+        self.address
+    }
+
+    /// The data fields, without address and checksum
+    pub fn content(&self) -> &'a [u8] {
+        self.content
+    }
+
+    pub fn fields(&self) -> Result<FieldsRef<'a>, ParseError> {
+        FieldsRef::new(self.content)
+    }
+
+    pub fn checksum_status(&self) -> ChecksumStatus {
+        checksum_status(self.characters)
+    }
+
+    /// Copies the sentence into an owned [NMEASentence], `None` if it is too long to be stored.
+    #[cfg(feature = "alloc")]
+    pub fn to_sentence(&self) -> Option<NMEASentence> {
+        let mut characters = self.characters.to_vec();
+        characters.extend_from_slice(b"\r\n");
+        let mut sentence = NMEASentence::from_bytes(&characters)?;
+        sentence.set_tag_block(self.tag_block().and_then(Result::ok));
+        Some(sentence)
+    }
+}
+
+/// The comma separated fields of a sentence as slices into the original buffer.\
+/// Dereferences to `[&[u8]]` so it can be handed to the formatters directly and offers the typed [Fields](crate::Fields) getters.
+#[derive(Debug, Clone)]
+pub struct FieldsRef<'a> {
+    fields: [&'a [u8]; NMEA_MAX_FIELDS],
+    length: usize,
+}
+
+impl<'a> FieldsRef<'a> {
+    pub fn new(content: &'a [u8]) -> Result<Self, ParseError> {
+        let mut fields: [&'a [u8]; NMEA_MAX_FIELDS] = [&[]; NMEA_MAX_FIELDS];
+        let mut length = 0;
+        for field in content.split(|c| *c == b',') {
+            *fields
+                .get_mut(length)
+                .ok_or(ParseError::TooManyFields(NMEA_MAX_FIELDS))? = field;
+            length += 1;
+        }
+        Ok(FieldsRef { fields, length })
+    }
+
+    pub fn get(&self, index: usize) -> Option<&'a [u8]> {
+        self.fields[..self.length].get(index).copied()
+    }
+}
+
+impl<'a> Deref for FieldsRef<'a> {
+    type Target = [&'a [u8]];
+
+    fn deref(&self) -> &Self::Target {
+        &self.fields[..self.length]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Address, Fields, Talker};
+
+    #[test]
+    fn test_parse_buffer() {
+        let buffer: &[u8] = b"\\s:r3669961,c:1503079517*70\\$SDDPT,87.4,0.0,*40\r\n\r\n$GPGSA,A,3,32,21,,,,,,,,,,,1.50,0.90,1.20*0E\r\n#invalid\r\n";
+
+        let sentences = SentenceRef::parse_all(buffer).collect::<Vec<_>>();
+        assert_eq!(sentences.len(), 3);
+
+        let dpt = sentences[0].as_ref().unwrap();
+        assert_eq!(dpt.as_bytes(), b"$SDDPT,87.4,0.0,*40");
+        assert_eq!(dpt.content(), b"87.4,0.0,");
+        assert_eq!(dpt.checksum_status(), ChecksumStatus::Valid);
+        assert_eq!(
+            dpt.tag_block().unwrap().unwrap().source.as_deref(),
+            Some("r3669961")
+        );
+        let Address::Approved(address) = dpt.address().address else {
+            panic!("expected an approved address");
+        };
+        assert_eq!(address.formatter, ['D', 'P', 'T']);
+        assert_eq!(address.talker, Talker::Sounder);
+
+        let fields = sentences[1].as_ref().unwrap().fields().unwrap();
+        assert_eq!(fields.len(), 17);
+        assert_eq!(fields.str(15).unwrap(), Some("0.90"));
+        assert_eq!(fields[16], b"1.20");
+        assert_eq!(fields.get(17), None);
+
+        let content = ",".repeat(NMEA_MAX_FIELDS);
+        assert_eq!(
+            FieldsRef::new(&content.as_bytes()[1..]).unwrap().len(),
+            NMEA_MAX_FIELDS
+        );
+        assert_eq!(
+            FieldsRef::new(content.as_bytes()).unwrap_err(),
+            ParseError::TooManyFields(NMEA_MAX_FIELDS)
+        );
+
+        assert_eq!(
+            sentences[2].as_ref().unwrap_err(),
+            &ParseError::BadStartDelimiter(b'#')
+        );
+
+        let owned = dpt.to_sentence().unwrap();
+        assert_eq!(owned.as_bytes(), b"$SDDPT,87.4,0.0,*40\r\n");
+        assert!(owned.tag_block().is_some());
+    }
+}
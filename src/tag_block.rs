@@ -0,0 +1,222 @@
+use crate::error::ParseError;
+use crate::primitives::escape::{decode_escaped, encode_escaped};
+use crate::sentence::parse_hex_byte;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use chrono::{DateTime, Utc};
+use core::fmt;
+use core::str::FromStr;
+
+pub const TAG_BLOCK_MAX_LENGTH: usize = 80;
+
+/// Sentence grouping parameter `g:1-2-1234`, sentence 1 of 2 in group 1234
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SentenceGroup {
+    pub sentence: u32,
+    pub total: u32,
+    pub id: u32,
+}
+
+/// IEC 61162-1 tag block, the `\s:r3669961,c:1503079517*70\` prefix in front of a sentence.\
+/// [unix_time](TagBlock.unix_time) `c:` UNIX time in seconds\
+/// [destination](TagBlock.destination) `d:` Destination identification\
+/// [group](TagBlock.group) `g:` Sentence grouping\
+/// [line_count](TagBlock.line_count) `n:` Line count\
+/// [relative_time](TagBlock.relative_time) `r:` Relative time\
+/// [source](TagBlock.source) `s:` Source identification\
+/// [text](TagBlock.text) `t:` Free text\
+/// [unknown](TagBlock.unknown) Parameter codes not covered above, kept in their original order\
+/// Reserved characters such as `,`, `*` or `\` in the text values are written as `^hh` escapes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagBlock {
+    pub unix_time: Option<u64>,
+    pub destination: Option<String>,
+    pub group: Option<SentenceGroup>,
+    pub line_count: Option<u32>,
+    pub relative_time: Option<u64>,
+    pub source: Option<String>,
+    pub text: Option<String>,
+    pub unknown: Vec<(char, String)>,
+}
+
+fn calculate_checksum(parameters: &[u8]) -> u8 {
+    parameters.iter().fold(0, |checksum, byte| checksum ^ byte)
+}
+
+fn parse_number<T: FromStr>(value: &str) -> Result<T, ParseError> {
+    T::from_str(value).map_err(|_| ParseError::BadTagBlock)
+}
+
+fn parse_text(value: &str) -> Result<String, ParseError> {
+    let value = decode_escaped(value.as_bytes()).map_err(|_| ParseError::BadTagBlock)?;
+    String::from_utf8(value.into_owned()).map_err(|_| ParseError::BadTagBlock)
+}
+
+fn escape_text(value: &str) -> String {
+    String::from_utf8_lossy(&encode_escaped(value.as_bytes())).into_owned()
+}
+
+impl TagBlock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the characters between the two `\` delimiters, checksum included.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let separator = data
+            .iter()
+            .rposition(|byte| *byte == b'*')
+            .ok_or(ParseError::BadTagBlock)?;
+        let (parameters, checksum) = (&data[..separator], &data[separator + 1..]);
+        let actual = parse_hex_byte(checksum).ok_or(ParseError::BadTagBlock)?;
+        let expected = calculate_checksum(parameters);
+        if expected != actual {
+            return Err(ParseError::BadChecksum { expected, actual });
+        }
+
+        let mut tag_block = TagBlock::new();
+        let parameters = core::str::from_utf8(parameters).map_err(|_| ParseError::BadTagBlock)?;
+        for parameter in parameters.split(',') {
+            let (code, value) = parameter.split_once(':').ok_or(ParseError::BadTagBlock)?;
+            let mut code_chars = code.chars();
+            let code = match (code_chars.next(), code_chars.next()) {
+                (Some(code), None) => code,
+                _ => return Err(ParseError::BadTagBlock),
+            };
+            match code {
+                'c' => tag_block.unix_time = Some(parse_number(value)?),
+                'd' => tag_block.destination = Some(parse_text(value)?),
+                'g' => {
+                    let mut numbers = value.splitn(3, '-');
+                    let mut next = || parse_number(numbers.next().unwrap_or_default());
+                    tag_block.group = Some(SentenceGroup {
+                        sentence: next()?,
+                        total: next()?,
+                        id: next()?,
+                    });
+                }
+                'n' => tag_block.line_count = Some(parse_number(value)?),
+                'r' => tag_block.relative_time = Some(parse_number(value)?),
+                's' => tag_block.source = Some(parse_text(value)?),
+                't' => tag_block.text = Some(parse_text(value)?),
+                code => tag_block.unknown.push((code, parse_text(value)?)),
+            }
+        }
+        Ok(tag_block)
+    }
+
+    /// The `c:` parameter as UTC time
+    pub fn datetime(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(i64::try_from(self.unix_time?).ok()?, 0)
+    }
+
+    fn parameters(&self) -> String {
+        let mut parameters: Vec<String> = vec![];
+        if let Some(source) = &self.source {
+            parameters.push(format!("s:{}", escape_text(source)));
+        }
+        if let Some(destination) = &self.destination {
+            parameters.push(format!("d:{}", escape_text(destination)));
+        }
+        if let Some(unix_time) = self.unix_time {
+            parameters.push(format!("c:{}", unix_time));
+        }
+        if let Some(relative_time) = self.relative_time {
+            parameters.push(format!("r:{}", relative_time));
+        }
+        if let Some(line_count) = self.line_count {
+            parameters.push(format!("n:{}", line_count));
+        }
+        if let Some(group) = self.group {
+            parameters.push(format!("g:{}-{}-{}", group.sentence, group.total, group.id));
+        }
+        if let Some(text) = &self.text {
+            parameters.push(format!("t:{}", escape_text(text)));
+        }
+        for (code, value) in self.unknown.iter() {
+            parameters.push(format!("{}:{}", code, escape_text(value)));
+        }
+        parameters.join(",")
+    }
+}
+
+impl fmt::Display for TagBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let parameters = self.parameters();
+        write!(
+            f,
+            "\\{}*{:02X}\\",
+            parameters,
+            calculate_checksum(parameters.as_bytes())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tag_block() {
+        let tag_block = TagBlock::parse(b"s:r3669961,c:1503079517*70").unwrap();
+
+        assert_eq!(tag_block.source.as_deref(), Some("r3669961"));
+        assert_eq!(tag_block.unix_time, Some(1503079517));
+        assert_eq!(
+            tag_block.datetime().unwrap().to_rfc3339(),
+            "2017-08-18T18:05:17+00:00"
+        );
+        assert_eq!(tag_block.to_string(), "\\s:r3669961,c:1503079517*70\\");
+
+        let tag_block = TagBlock::parse(b"g:1-2-1234,s:SI0001,n:7*6B").unwrap();
+        assert_eq!(
+            tag_block.group,
+            Some(SentenceGroup {
+                sentence: 1,
+                total: 2,
+                id: 1234
+            })
+        );
+        assert_eq!(tag_block.line_count, Some(7));
+
+        assert_eq!(
+            TagBlock::parse(b"s:r3669961,c:1503079517*71").unwrap_err(),
+            ParseError::BadChecksum {
+                expected: 0x70,
+                actual: 0x71
+            }
+        );
+        assert_eq!(
+            TagBlock::parse(b"c:now*2F").unwrap_err(),
+            ParseError::BadTagBlock
+        );
+        assert_eq!(
+            TagBlock::parse(b"c:1*+1").unwrap_err(),
+            ParseError::BadTagBlock
+        );
+    }
+
+    #[test]
+    fn test_tag_block_reserved_characters() {
+        let tag_block = TagBlock {
+            source: Some(String::from("GP\\01")),
+            text: Some(String::from("DEPTH ALARM, 5.0 m *")),
+            unknown: vec![('x', String::from("a,b:c"))],
+            ..TagBlock::new()
+        };
+
+        let encoded = tag_block.to_string();
+        let parameters = &encoded[1..encoded.len() - 1];
+        assert_eq!(
+            &parameters[..parameters.len() - 3],
+            "s:GP^5C01,t:DEPTH ALARM^2C 5.0 m ^2A,x:a^2Cb:c"
+        );
+        assert_eq!(TagBlock::parse(parameters.as_bytes()).unwrap(), tag_block);
+        assert_eq!(
+            TagBlock::parse(b"t:^2*22").unwrap_err(),
+            ParseError::BadTagBlock
+        );
+    }
+}
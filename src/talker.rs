@@ -0,0 +1,263 @@
+use core::fmt;
+
+/// Satellite systems a GNSS talker can report on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Constellation {
+    Gps,
+    Glonass,
+    Galileo,
+    BeiDou,
+    Qzss,
+    NavIC,
+}
+
+/// Identifier that is not in the talker table, only created by [Talker::from_code]
+/// so every known identifier has exactly one representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnknownTalker([u8; 2]);
+
+impl UnknownTalker {
+    pub fn code(&self) -> [u8; 2] {
+        self.0
+    }
+}
+
+macro_rules! talkers {
+    ($($(#[$doc:meta])* $variant:ident = $code:literal,)*) => {
+        /// Talker identifier of an approved or query sentence, IEC 61162-1 table 5.\
+        /// Identifiers missing from the table are kept as [Talker::Unknown].
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum Talker {
+            $($(#[$doc])* $variant,)*
+            Unknown(UnknownTalker),
+        }
+
+        impl Talker {
+            pub fn from_code(code: [u8; 2]) -> Self {
+                match &code {
+                    $($code => Talker::$variant,)*
+                    _ => Talker::Unknown(UnknownTalker(code)),
+                }
+            }
+
+            /// The two characters of the identifier, e.g. `GP`
+            pub fn code(&self) -> [u8; 2] {
+                match self {
+                    $(Talker::$variant => *$code,)*
+                    Talker::Unknown(unknown) => unknown.code(),
+                }
+            }
+        }
+    };
+}
+
+talkers! {
+    /// Independent AIS base station
+    AisBaseStation = b"AB",
+    /// Dependent AIS base station
+    AisDependentBaseStation = b"AD",
+    /// Autopilot, general
+    Autopilot = b"AG",
+    /// Mobile AIS station
+    Ais = b"AI",
+    /// AIS aids to navigation station
+    AisAidToNavigation = b"AN",
+    /// Autopilot, magnetic
+    AutopilotMagnetic = b"AP",
+    /// AIS receiving station
+    AisReceiving = b"AR",
+    /// AIS limited base station
+    AisLimitedBaseStation = b"AS",
+    /// AIS transmitting station
+    AisTransmitting = b"AT",
+    /// AIS simplex repeater station
+    AisRepeater = b"AX",
+    /// BeiDou, identifier used before the introduction of `GB`
+    BeiDouLegacy = b"BD",
+    /// Bilge system
+    Bilge = b"BI",
+    /// Bridge navigational watch alarm system
+    WatchAlarm = b"BN",
+    /// Central alarm management
+    CentralAlarm = b"CA",
+    /// Digital selective calling
+    Dsc = b"CD",
+    /// Data receiver
+    DataReceiver = b"CR",
+    /// Satellite communication
+    SatelliteCommunication = b"CS",
+    /// MF/HF radiotelephone
+    RadioTelephone = b"CT",
+    /// VHF radiotelephone
+    RadioTelephoneVhf = b"CV",
+    /// Scanning receiver
+    ScanningReceiver = b"CX",
+    /// Direction finder
+    DirectionFinder = b"DF",
+    /// Velocity sensor, speed log, water, magnetic
+    SpeedLogMagnetic = b"DM",
+    /// Dynamic positioning
+    DynamicPositioning = b"DP",
+    /// Duplex repeater station
+    DuplexRepeater = b"DU",
+    /// Electronic chart display and information system
+    Ecdis = b"EC",
+    /// Emergency position indicating radio beacon
+    Epirb = b"EP",
+    /// Engine room monitoring
+    EngineRoom = b"ER",
+    /// Fire door controller
+    FireDoor = b"FD",
+    /// Fire extinguisher system
+    FireExtinguisher = b"FE",
+    /// Fire detection point
+    FireDetection = b"FR",
+    /// Fire sprinkler system
+    FireSprinkler = b"FS",
+    Galileo = b"GA",
+    BeiDou = b"GB",
+    NavIC = b"GI",
+    Glonass = b"GL",
+    /// Combined solution of several satellite systems
+    Gnss = b"GN",
+    Gps = b"GP",
+    Qzss = b"GQ",
+    /// Heading sensor, compass, magnetic
+    CompassMagnetic = b"HC",
+    /// Hull door controller
+    HullDoor = b"HD",
+    /// Heading sensor, gyro, north seeking
+    GyroNorthSeeking = b"HE",
+    /// Heading sensor, fluxgate
+    Fluxgate = b"HF",
+    /// Heading sensor, gyro, non-north seeking
+    GyroNonNorthSeeking = b"HN",
+    /// Hull stress monitoring
+    HullStress = b"HS",
+    /// Integrated instrumentation
+    IntegratedInstrumentation = b"II",
+    /// Integrated navigation
+    IntegratedNavigation = b"IN",
+    /// Loran C
+    LoranC = b"LC",
+    /// Navigation light controller
+    NavigationLight = b"NL",
+    /// Radar and radar plotting
+    Radar = b"RA",
+    /// Propulsion machinery including remote control
+    Propulsion = b"RC",
+    /// Rudder angle indicator
+    RudderAngle = b"RI",
+    /// Physical shore AIS station
+    AisShoreStation = b"SA",
+    /// Sounder, depth
+    Sounder = b"SD",
+    /// Steering gear, steering engine
+    SteeringGear = b"SG",
+    /// Electronic positioning system, other or general
+    PositioningSystem = b"SN",
+    /// Sounder, scanning
+    ScanningSounder = b"SS",
+    /// Turn rate indicator
+    TurnRate = b"TI",
+    /// Microprocessor controller
+    Microprocessor = b"UP",
+    /// Velocity sensor, doppler, other or general
+    Doppler = b"VD",
+    /// Velocity sensor, speed log, water, mechanical
+    SpeedLogMechanical = b"VW",
+    /// Voyage data recorder
+    VoyageDataRecorder = b"VR",
+    /// Watertight door controller
+    WatertightDoor = b"WD",
+    /// Weather instruments
+    Weather = b"WI",
+    /// Water level detection system
+    WaterLevel = b"WL",
+    /// Transducer
+    Transducer = b"YX",
+    /// Timekeeper, atomic clock
+    AtomicClock = b"ZA",
+    /// Timekeeper, chronometer
+    Chronometer = b"ZC",
+    /// Timekeeper, quartz
+    QuartzClock = b"ZQ",
+    /// Timekeeper, radio update
+    RadioClock = b"ZV",
+}
+
+impl Talker {
+    /// The talker is a satellite navigation receiver, a single constellation or [Talker::Gnss]
+    pub fn is_gnss(&self) -> bool {
+        *self == Talker::Gnss || self.constellation().is_some()
+    }
+
+    /// The satellite system of a single constellation receiver, `None` for [Talker::Gnss] and non GNSS talkers
+    pub fn constellation(&self) -> Option<Constellation> {
+        match self {
+            Talker::Gps => Some(Constellation::Gps),
+            Talker::Glonass => Some(Constellation::Glonass),
+            Talker::Galileo => Some(Constellation::Galileo),
+            Talker::BeiDou | Talker::BeiDouLegacy => Some(Constellation::BeiDou),
+            Talker::Qzss => Some(Constellation::Qzss),
+            Talker::NavIC => Some(Constellation::NavIC),
+            _ => None,
+        }
+    }
+
+    /// The talker belongs to an AIS station
+    pub fn is_ais(&self) -> bool {
+        matches!(
+            self,
+            Talker::AisBaseStation
+                | Talker::AisDependentBaseStation
+                | Talker::Ais
+                | Talker::AisAidToNavigation
+                | Talker::AisReceiving
+                | Talker::AisLimitedBaseStation
+                | Talker::AisTransmitting
+                | Talker::AisRepeater
+                | Talker::AisShoreStation
+        )
+    }
+}
+
+impl From<[u8; 2]> for Talker {
+    fn from(code: [u8; 2]) -> Self {
+        Talker::from_code(code)
+    }
+}
+
+impl fmt::Display for Talker {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [first, second] = self.code();
+        write!(f, "{}{}", first as char, second as char)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_talker_codes() {
+        assert_eq!(Talker::from_code(*b"GP"), Talker::Gps);
+        assert_eq!(
+            Talker::from_code(*b"BD").constellation(),
+            Some(Constellation::BeiDou)
+        );
+        assert_eq!(
+            Talker::from_code(*b"GB").constellation(),
+            Some(Constellation::BeiDou)
+        );
+        assert!(Talker::Gnss.is_gnss());
+        assert_eq!(Talker::Gnss.constellation(), None);
+        assert!(!Talker::IntegratedInstrumentation.is_gnss());
+        assert!(Talker::Ais.is_ais());
+
+        let unknown = Talker::from_code(*b"P1");
+        assert!(matches!(unknown, Talker::Unknown(code) if code.code() == *b"P1"));
+        assert_eq!(unknown.to_string(), "P1");
+        assert_eq!(Talker::NavIC.to_string(), "GI");
+    }
+}